@@ -0,0 +1,84 @@
+use std::{error::Error, fmt::Display};
+
+/// A parse failure pointing at the offending part of the input.
+///
+/// `line` and `column` are 1-based, `column` counts characters rather than bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        snippet: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> Self {
+        Self {
+            line,
+            column,
+            snippet: snippet.into(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Creates an error for `snippet` in the line with the 0-based index `line_idx`.
+    ///
+    /// `snippet` has to be a subslice of `line` so its column can be recovered.
+    pub fn at(line_idx: usize, line: &str, snippet: &str, expected: impl Into<String>) -> Self {
+        let offset = (snippet.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|offset| *offset <= line.len())
+            .expect("snippet is not part of the line");
+
+        Self::new(
+            line_idx + 1,
+            line[..offset].chars().count() + 1,
+            snippet,
+            expected,
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found `{}`",
+            self.line, self.column, self.expected, self.snippet
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case("Game 1: 3 blue", 6..7 => (1, 7))]
+    #[test_case("Game 1: 3 blue", 10..14 => (1, 11))]
+    #[test_case("Gäme 1: 3 blue", 11..15 => (1, 11))]
+    #[test_case("Game 1: 3 blue", 14..14 => (1, 15))]
+    fn test_at(line: &str, span: std::ops::Range<usize>) -> (usize, usize) {
+        let err = ParseError::at(0, line, &line[span], "something");
+
+        (err.line, err.column)
+    }
+
+    #[test]
+    fn test_display() {
+        let err = ParseError::new(3, 9, "purple", "a color");
+
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 9: expected a color, found `purple`"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt::Display;
+
+mod error;
+
+pub use error::ParseError;
+
+/// A single day's puzzle, split into parsing and the two parts operating on the parsed input.
+///
+/// The parts may still fail on input that only one of them can interpret.
+pub trait Solution {
+    type Input;
+    type Answer: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer, ParseError>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer, ParseError>;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+
+    pub fn solve<S: Solution>(&self, input: &S::Input) -> Result<S::Answer, ParseError> {
+        match self {
+            Self::One => S::part1(input),
+            Self::Two => S::part2(input),
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            other => Err(other),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+pub fn solve<S: Solution>(input: &str, part: Part) -> Result<S::Answer, ParseError> {
+    let input = S::parse(input)?;
+    part.solve::<S>(&input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    struct Lengths;
+
+    impl Solution for Lengths {
+        type Input = Vec<usize>;
+        type Answer = usize;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input
+                .lines()
+                .enumerate()
+                .map(|(idx, line)| match line.find(' ') {
+                    Some(pos) => Err(ParseError::at(idx, line, &line[pos..pos + 1], "a letter")),
+                    None => Ok(line.len()),
+                })
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Result<Self::Answer, ParseError> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Self::Input) -> Result<Self::Answer, ParseError> {
+            Ok(input.iter().copied().max().unwrap_or_default())
+        }
+    }
+
+    #[test_case(1 => Ok(Part::One))]
+    #[test_case(2 => Ok(Part::Two))]
+    #[test_case(3 => Err(3))]
+    fn test_part_from_number(value: u8) -> Result<Part, u8> {
+        Part::try_from(value)
+    }
+
+    #[test_case("a\nbb\nccc", Part::One => Ok(6))]
+    #[test_case("a\nbb\nccc", Part::Two => Ok(3))]
+    #[test_case("a\nb b", Part::One => Err(ParseError::new(2, 2, " ", "a letter")))]
+    fn test_solve(input: &str, part: Part) -> Result<usize, ParseError> {
+        solve::<Lengths>(input, part)
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use aoc01::Day01;
+use aoc02::Day02;
+use aoc03::Day03;
+use aoc04::Day04;
+use aoc_core::{ParseError, Part, Solution};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::{json, Value};
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day
+    Run {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=DAYS as i64))]
+        day: u8,
+
+        /// Part to solve, both parts are solved if omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Path to the puzzle input, `-` reads from stdin
+        #[arg(short, long, default_value = "-")]
+        input: PathBuf,
+
+        #[arg(short, long, value_enum, default_value_t = Format::Human)]
+        format: Format,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Human,
+    Json,
+}
+
+const DAYS: u8 = 4;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input: path,
+            format,
+        } => {
+            let input = match read_input(&path) {
+                Ok(input) => input,
+                Err(err) => {
+                    eprintln!("Could not read input {}: {}", path.display(), err);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let parts = match part {
+                Some(part) => vec![Part::try_from(part).unwrap()],
+                None => Part::ALL.to_vec(),
+            };
+
+            match solve_day(day, &input, &parts) {
+                Ok(answers) => print_answers(day, &answers, format),
+                Err(err) => {
+                    eprintln!("{}", render_error(&err, &input, &path));
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn read_input(path: &Path) -> io::Result<String> {
+    if path.as_os_str() == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+fn solve_day(day: u8, input: &str, parts: &[Part]) -> Result<Vec<(Part, Value)>, ParseError> {
+    match day {
+        1 => solve::<Day01>(input, parts),
+        2 => solve::<Day02>(input, parts),
+        3 => solve::<Day03>(input, parts),
+        4 => solve::<Day04>(input, parts),
+        _ => unreachable!("day is validated by the argument parser"),
+    }
+}
+
+/// Solves `parts`, keeping the answers as JSON values so numbers stay numbers.
+fn solve<S>(input: &str, parts: &[Part]) -> Result<Vec<(Part, Value)>, ParseError>
+where
+    S: Solution,
+    S::Answer: Into<Value>,
+{
+    let input = S::parse(input)?;
+
+    parts
+        .iter()
+        .map(|part| Ok((*part, part.solve::<S>(&input)?.into())))
+        .collect()
+}
+
+/// Renders a parse error with the offending source line and a caret marker under the snippet.
+fn render_error(err: &ParseError, source: &str, path: &Path) -> String {
+    let name = if path.as_os_str() == "-" {
+        "<stdin>".into()
+    } else {
+        path.display().to_string()
+    };
+    let line = source.lines().nth(err.line - 1).unwrap_or_default();
+    let gutter = " ".repeat(err.line.to_string().len());
+
+    format!(
+        "error: expected {}, found `{}`\n\
+         {gutter}--> {}:{}:{}\n\
+         {gutter} |\n\
+         {} | {}\n\
+         {gutter} | {}{}",
+        err.expected,
+        err.snippet,
+        name,
+        err.line,
+        err.column,
+        err.line,
+        line,
+        " ".repeat(err.column - 1),
+        "^".repeat(err.snippet.chars().count().max(1)),
+    )
+}
+
+fn print_answers(day: u8, answers: &[(Part, Value)], format: Format) {
+    match format {
+        Format::Human => {
+            for (part, answer) in answers {
+                println!("Day {}, part {}: {}", day, part, answer);
+            }
+        }
+        Format::Json => println!("{}", json_answers(day, answers)),
+    }
+}
+
+fn json_answers(day: u8, answers: &[(Part, Value)]) -> Value {
+    let answers: Vec<_> = answers
+        .iter()
+        .map(|(part, answer)| json!({ "part": part.number(), "answer": answer }))
+        .collect();
+
+    json!({ "day": day, "answers": answers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_error() {
+        let source = "Game 1: 3 blue\nGame 2: 1 red, 2 pur-ple";
+        let err = solve::<Day02>(source, &Part::ALL).unwrap_err();
+
+        assert_eq!(
+            render_error(&err, source, Path::new("input.txt")),
+            "error: expected a colour name, found `pur-ple`
+ --> input.txt:2:18
+  |
+2 | Game 2: 1 red, 2 pur-ple
+  |                  ^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn test_json_answers() {
+        let source = "Game 1: 3 blue, 4 red, 1 green\nGame 2: 1 red, 2 green, 5 blue";
+        let answers = solve::<Day02>(source, &Part::ALL).unwrap();
+
+        assert_eq!(
+            json_answers(2, &answers).to_string(),
+            r#"{"answers":[{"answer":3,"part":1},{"answer":22,"part":2}],"day":2}"#
+        );
+    }
+}
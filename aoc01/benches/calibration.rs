@@ -0,0 +1,39 @@
+use std::hint::black_box;
+
+use aoc01::{DigitScanner, DigitVocabulary, NUMBERS};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+#[path = "../src/naive.rs"]
+mod naive;
+
+const INPUT: &str = include_str!("../input.txt");
+
+fn calibration(c: &mut Criterion) {
+    let mut group = c.benchmark_group("calibration");
+
+    group.bench_function("naive", |b| {
+        b.iter(|| {
+            INPUT
+                .lines()
+                .map(|line| {
+                    naive::read_calibration_number(black_box(line), &NUMBERS).unwrap() as u32
+                })
+                .sum::<u32>()
+        })
+    });
+
+    let scanner = DigitScanner::new(&DigitVocabulary::english());
+    group.bench_function("scanner", |b| {
+        b.iter(|| {
+            INPUT
+                .lines()
+                .map(|line| scanner.read_calibration_number(black_box(line)).unwrap() as u32)
+                .sum::<u32>()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, calibration);
+criterion_main!(benches);
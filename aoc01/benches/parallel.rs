@@ -0,0 +1,58 @@
+use std::{env, hint::black_box};
+
+use aoc01::{read_document_par, DigitScanner, DigitVocabulary};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rayon::ThreadPoolBuilder;
+
+const INPUT: &str = include_str!("../input.txt");
+
+/// Repeats the puzzle input up to `lines` lines, 100 million unless `AOC01_BENCH_LINES` is set.
+fn synthetic() -> (String, usize) {
+    let lines = env::var("AOC01_BENCH_LINES")
+        .ok()
+        .and_then(|lines| lines.parse().ok())
+        .unwrap_or(100_000_000);
+
+    let mut doc = String::new();
+    for line in INPUT.lines().cycle().take(lines) {
+        doc.push_str(line);
+        doc.push('\n');
+    }
+
+    (doc, lines)
+}
+
+fn parallel(c: &mut Criterion) {
+    let (doc, lines) = synthetic();
+    let scanner = DigitScanner::new(&DigitVocabulary::english());
+
+    let mut group = c.benchmark_group("parallel");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(lines as u64));
+
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            black_box(&doc)
+                .lines()
+                .map(|line| scanner.read_calibration_number(line).unwrap() as u64)
+                .sum::<u64>()
+        })
+    });
+
+    let max_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    for threads in (0..).map(|exp| 1 << exp).take_while(|n| *n <= max_threads) {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+
+        group.bench_with_input(BenchmarkId::new("rayon", threads), &threads, |b, _| {
+            b.iter(|| pool.install(|| read_document_par(black_box(&doc), &scanner).unwrap()))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, parallel);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+#![no_main]
+
+use aoc01::{
+    explain, read_calibration_number, read_document, tokens, DigitScanner, DigitVocabulary,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // invalid UTF-8 has to surface as an error of the streaming reader
+    let _ = read_document(data);
+
+    let Ok(doc) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let unicode = DigitScanner::new(&DigitVocabulary::english().unicode_digits(true));
+    for line in doc.lines() {
+        let _ = read_calibration_number(line);
+        let _ = unicode.read_calibration_number(line);
+        for token in tokens(line) {
+            assert!(line.get(token.span).is_some());
+        }
+    }
+    let _ = explain(doc, &unicode);
+
+    if let Ok(vocabulary) = doc.parse::<DigitVocabulary>() {
+        let _ = DigitScanner::new(&vocabulary).read_calibration_number(doc);
+    }
+});
@@ -0,0 +1,131 @@
+use std::fmt::Write;
+
+use serde_json::{json, Value};
+
+use crate::{DigitScanner, NumberPos};
+
+/// How a single calibration line was read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation<'a> {
+    /// 1-based line number
+    pub line: usize,
+    pub text: &'a str,
+    pub first: Option<NumberPos>,
+    pub last: Option<NumberPos>,
+}
+
+impl Explanation<'_> {
+    /// The calibration number, `None` if the line has no digit.
+    pub fn value(&self) -> Option<u8> {
+        Some(self.first?.get_val() * 10 + self.last?.get_val())
+    }
+}
+
+/// Explains every line of `doc`, including the ones without digits.
+pub fn explain<'a>(doc: &'a str, scanner: &DigitScanner) -> Vec<Explanation<'a>> {
+    doc.lines()
+        .enumerate()
+        .map(|(idx, text)| Explanation {
+            line: idx + 1,
+            text,
+            first: scanner.first(text),
+            last: scanner.last(text),
+        })
+        .collect()
+}
+
+fn describe(num: Option<NumberPos>) -> String {
+    match num {
+        Some(num) => format!("{} {} @{}", num.kind(), num.get_val(), num.get_pos()),
+        None => "-".to_owned(),
+    }
+}
+
+/// Renders the explanations as an aligned table with one row per line.
+pub fn explain_table(explanations: &[Explanation]) -> String {
+    let mut table = format!(
+        "{:>5}  {:<12}  {:<12}  {:>5}  {}\n",
+        "line", "first", "last", "value", "text"
+    );
+    for explanation in explanations {
+        let value = explanation
+            .value()
+            .map_or_else(|| "-".to_owned(), |val| val.to_string());
+        writeln!(
+            table,
+            "{:>5}  {:<12}  {:<12}  {:>5}  {}",
+            explanation.line,
+            describe(explanation.first),
+            describe(explanation.last),
+            value,
+            explanation.text
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+pub fn explain_json(explanations: &[Explanation]) -> Value {
+    let describe = |num: Option<NumberPos>| {
+        num.map(|num| json!({ "kind": num.kind(), "pos": num.get_pos(), "value": num.get_val() }))
+    };
+
+    explanations
+        .iter()
+        .map(|explanation| {
+            json!({
+                "line": explanation.line,
+                "text": explanation.text,
+                "first": describe(explanation.first),
+                "last": describe(explanation.last),
+                "value": explanation.value(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::SPELLED;
+
+    #[test]
+    fn test_explain() {
+        let explanations = explain("two1nine\nabc\n7", &SPELLED);
+
+        assert_eq!(
+            explanations
+                .iter()
+                .map(Explanation::value)
+                .collect::<Vec<_>>(),
+            [Some(29), None, Some(77)]
+        );
+        assert_eq!(explanations[0].first, Some(NumberPos::String(0, 2)));
+        assert_eq!(explanations[0].last, Some(NumberPos::String(4, 9)));
+    }
+
+    #[test]
+    fn test_explain_table() {
+        let explanations = explain("xtwone3four\nabc", &SPELLED);
+
+        assert_eq!(
+            explain_table(&explanations),
+            " line  first         last          value  text
+    1  word 2 @1     word 4 @7        24  xtwone3four
+    2  -             -                 -  abc
+"
+        );
+    }
+
+    #[test]
+    fn test_explain_json() {
+        let explanations = explain("a1", &SPELLED);
+
+        assert_eq!(
+            explain_json(&explanations).to_string(),
+            r#"[{"first":{"kind":"digit","pos":1,"value":1},"last":{"kind":"digit","pos":1,"value":1},"line":1,"text":"a1","value":11}]"#
+        );
+    }
+}
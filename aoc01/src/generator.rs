@@ -0,0 +1,313 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{NUMBERS, SPELLED};
+
+/// Attempts at a noisy line before falling back to plain digits.
+const ATTEMPTS: usize = 100;
+
+/// Pairs of words sharing a letter, like `oneight`, where the first word ends in the
+/// letter the second one starts with.
+pub(crate) const OVERLAPS: [(u8, u8); 8] = [
+    (1, 8),
+    (2, 1),
+    (3, 8),
+    (5, 8),
+    (7, 9),
+    (8, 2),
+    (8, 3),
+    (9, 8),
+];
+
+/// Generates calibration documents with known answers for tests and benchmarks.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    seed: u64,
+    lines: usize,
+    noise: Vec<char>,
+    overlap: f64,
+    target: Option<u64>,
+}
+
+/// A generated document together with the calibration number of every line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub doc: String,
+    pub values: Vec<u8>,
+}
+
+impl Generated {
+    pub fn total(&self) -> u64 {
+        self.values.iter().map(|val| *val as u64).sum()
+    }
+}
+
+impl Generator {
+    pub fn new(seed: u64, lines: usize) -> Self {
+        Self {
+            seed,
+            lines,
+            noise: ('a'..='z').collect(),
+            overlap: 0.25,
+            target: None,
+        }
+    }
+
+    /// Characters filling the gaps between numbers, ASCII digits are left out.
+    pub fn noise(mut self, alphabet: &str) -> Self {
+        self.noise = alphabet.chars().filter(|c| !c.is_ascii_digit()).collect();
+        self
+    }
+
+    /// Probability for the first and the last number to be written overlapping with
+    /// another word, like `oneight` or `eighthree`.
+    pub fn overlap(mut self, probability: f64) -> Self {
+        self.overlap = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Makes the calibration numbers sum up to `total`.
+    pub fn target(mut self, total: u64) -> Self {
+        self.target = Some(total);
+        self
+    }
+
+    /// Generates the document, `None` if the target total can not be reached.
+    ///
+    /// Every line is worth between 11 and 99, but never a multiple of ten.
+    pub fn generate(&self) -> Option<Generated> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let mut values: Vec<u8> = (0..self.lines)
+            .map(|_| rng.gen_range(1..=9) * 10 + rng.gen_range(1..=9))
+            .collect();
+        if let Some(target) = self.target {
+            balance(&mut values, target, &mut rng)?;
+        }
+
+        let mut doc = String::new();
+        for val in &values {
+            doc.push_str(&self.line(*val, &mut rng));
+            doc.push('\n');
+        }
+
+        Some(Generated { doc, values })
+    }
+
+    fn line(&self, val: u8, rng: &mut StdRng) -> String {
+        let (first, last) = (val / 10, val % 10);
+
+        for _ in 0..ATTEMPTS {
+            let mut line = self.gap(rng);
+
+            let next: Vec<_> = OVERLAPS.iter().filter(|(word, _)| *word == first).collect();
+            if !next.is_empty() && rng.gen_bool(self.overlap) {
+                let (_, next) = **next.choose(rng).unwrap();
+                line.push_str(word(first));
+                line.push_str(&word(next)[1..]);
+            } else {
+                line.push_str(&self.number(first, rng));
+            }
+
+            for _ in 0..rng.gen_range(0..=3) {
+                line.push_str(&self.gap(rng));
+                line.push_str(&self.number(rng.gen_range(0..=9), rng));
+            }
+            line.push_str(&self.gap(rng));
+
+            let previous: Vec<_> = OVERLAPS.iter().filter(|(_, word)| *word == last).collect();
+            if !previous.is_empty() && rng.gen_bool(self.overlap) {
+                let (prev, _) = **previous.choose(rng).unwrap();
+                let prev = word(prev);
+                line.push_str(&prev[..prev.len() - 1]);
+                line.push_str(word(last));
+            } else {
+                line.push_str(&self.number(last, rng));
+            }
+            line.push_str(&self.gap(rng));
+
+            // noise may spell out numbers of its own
+            if SPELLED.read_calibration_number(&line) == Some(val) {
+                return line;
+            }
+        }
+
+        format!("{}{}", first, last)
+    }
+
+    fn number(&self, val: u8, rng: &mut StdRng) -> String {
+        match val {
+            1..=9 if rng.gen_bool(0.5) => word(val).to_owned(),
+            _ => val.to_string(),
+        }
+    }
+
+    fn gap(&self, rng: &mut StdRng) -> String {
+        if self.noise.is_empty() {
+            return String::new();
+        }
+
+        (0..rng.gen_range(0..=4))
+            .map(|_| *self.noise.choose(rng).unwrap())
+            .collect()
+    }
+}
+
+pub(crate) fn word(val: u8) -> &'static str {
+    NUMBERS[val as usize - 1]
+}
+
+/// Shifts the values until they sum up to `target`, keeping every one a valid line value.
+///
+/// Tens and units are balanced separately. Both digits stay within 1..=9, so no value
+/// ever becomes a multiple of ten.
+fn balance(values: &mut [u8], target: u64, rng: &mut StdRng) -> Option<()> {
+    let lines = values.len() as u64;
+    let current: u64 = values.iter().map(|val| (val % 10) as u64).sum();
+
+    // the units sum ends in the target's last digit, take the feasible one closest to now
+    let first = lines + (target + 10 - lines % 10) % 10;
+    let units = (first..=9 * lines)
+        .step_by(10)
+        .filter(|units| *units <= target && (lines..=9 * lines).contains(&((target - units) / 10)))
+        .min_by_key(|units| units.abs_diff(current))?;
+
+    let mut order: Vec<_> = (0..values.len()).collect();
+    order.shuffle(rng);
+    let mut tens: Vec<_> = values.iter().map(|val| val / 10).collect();
+    let mut ones: Vec<_> = values.iter().map(|val| val % 10).collect();
+    spread(&mut tens, (target - units) / 10, &order);
+    spread(&mut ones, units, &order);
+
+    for (val, (ten, one)) in values.iter_mut().zip(tens.iter().zip(&ones)) {
+        *val = ten * 10 + one;
+    }
+
+    Some(())
+}
+
+/// Moves digits within 1..=9 until they sum up to `target`, which has to be in reach.
+fn spread(digits: &mut [u8], target: u64, order: &[usize]) {
+    let mut diff = target as i64 - digits.iter().map(|digit| *digit as i64).sum::<i64>();
+    for idx in order {
+        let digit = digits[*idx] as i64;
+        let next = (digit + diff).clamp(1, 9);
+        diff -= next - digit;
+        digits[*idx] = next as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    use crate::read_document_with;
+
+    #[test_case(0, 1000, 55_000)]
+    #[test_case(1, 1000, 11_000)]
+    #[test_case(2, 1000, 99_000)]
+    #[test_case(3, 2, 40)]
+    #[test_case(4, 1, 37)]
+    #[test_case(5, 2, 31)]
+    #[test_case(6, 100, 1109)]
+    #[test_case(7, 100, 9821)]
+    fn test_target(seed: u64, lines: usize, target: u64) {
+        let generated = Generator::new(seed, lines)
+            .target(target)
+            .generate()
+            .unwrap();
+
+        assert_eq!(generated.total(), target);
+        assert_eq!(
+            read_document_with(&generated.doc, &SPELLED),
+            Ok(target as u32)
+        );
+        for (line, val) in generated.doc.lines().zip(&generated.values) {
+            assert_eq!(
+                SPELLED.read_calibration_number(line),
+                Some(*val),
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test_case(1, 40)]
+    #[test_case(1, 100)]
+    #[test_case(10, 109)]
+    #[test_case(10, 991)]
+    fn test_unreachable(lines: usize, target: u64) {
+        assert_eq!(Generator::new(0, lines).target(target).generate(), None);
+    }
+
+    #[test]
+    fn test_every_target() {
+        for lines in 1..=4 {
+            for target in 11 * lines as u64..=99 * lines as u64 {
+                // a single line can not be worth a multiple of ten
+                if lines == 1 && target % 10 == 0 {
+                    continue;
+                }
+
+                for seed in 0..3 {
+                    let generated = Generator::new(seed, lines).target(target).generate();
+                    assert_eq!(generated.map(|gen| gen.total()), Some(target));
+                }
+            }
+        }
+    }
+
+    #[test_case("", 0.0)]
+    #[test_case("xyz", 1.0)]
+    #[test_case("onetwhr", 0.5)]
+    #[test_case("éü€", 0.5)]
+    fn test_noise(alphabet: &str, overlap: f64) {
+        let generated = Generator::new(7, 500)
+            .noise(alphabet)
+            .overlap(overlap)
+            .generate()
+            .unwrap();
+
+        for (line, val) in generated.doc.lines().zip(&generated.values) {
+            assert_eq!(
+                SPELLED.read_calibration_number(line),
+                Some(*val),
+                "{}",
+                line
+            );
+            assert!(
+                line.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || alphabet.contains(c)),
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let generated = Generator::new(3, 200).overlap(1.0).generate().unwrap();
+
+        let overlapping = [
+            "oneight",
+            "twone",
+            "threeight",
+            "fiveight",
+            "sevenine",
+            "eightwo",
+        ];
+        assert!(overlapping.iter().any(|pair| generated.doc.contains(pair)));
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let generator = Generator::new(42, 100).target(5000);
+
+        assert_eq!(generator.generate(), generator.generate());
+        assert_ne!(
+            generator.generate(),
+            Generator::new(43, 100).target(5000).generate()
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::{io::BufRead, ops::Range, sync::LazyLock};
+
+use aoc_core::{ParseError, Solution};
+
+mod explain;
+mod generator;
+#[cfg(test)]
+mod naive;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(test)]
+mod properties;
+mod rule;
+mod scanner;
+mod stream;
+mod vocabulary;
+
+pub use explain::{explain, explain_json, explain_table, Explanation};
+pub use generator::{Generated, Generator};
+#[cfg(feature = "parallel")]
+pub use parallel::{read_document_par, read_document_report_par};
+pub use rule::{
+    read_document_by, read_document_report, CalibrationRule, Checksum, Concat, FirstLast,
+    MissingDigits, Report, Windows,
+};
+pub use scanner::DigitScanner;
+pub use stream::{read_document_from, read_document_report_from, ReadError};
+pub use vocabulary::DigitVocabulary;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = String;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer, ParseError> {
+        read_document_with(input, &DIGITS)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer, ParseError> {
+        read_document_with(input, &SPELLED)
+    }
+}
+
+pub const NUMBERS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+static DIGITS: LazyLock<DigitScanner> =
+    LazyLock::new(|| DigitScanner::new(&DigitVocabulary::new()));
+static SPELLED: LazyLock<DigitScanner> =
+    LazyLock::new(|| DigitScanner::new(&DigitVocabulary::english()));
+
+/// A digit or spelled number with the byte offset of its start in the line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberPos {
+    Digit(usize, u8),
+    String(usize, u8),
+}
+
+impl NumberPos {
+    pub fn get_pos(&self) -> usize {
+        match self {
+            Self::Digit(pos, _) => *pos,
+            Self::String(pos, _) => *pos,
+        }
+    }
+
+    pub fn get_val(&self) -> u8 {
+        match self {
+            Self::Digit(_, val) => *val,
+            Self::String(_, val) => *val,
+        }
+    }
+
+    /// `digit` or `word`, for displaying matches.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Digit(..) => "digit",
+            Self::String(..) => "word",
+        }
+    }
+}
+
+/// A digit or spelled number found in a line, `span` being its byte range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub span: Range<usize>,
+    pub num: NumberPos,
+}
+
+impl Token {
+    pub fn is_spelled(&self) -> bool {
+        matches!(self.num, NumberPos::String(..))
+    }
+}
+
+/// Every digit and spelled number in `line`, see [`DigitScanner::tokens`].
+pub fn tokens(line: &str) -> impl Iterator<Item = Token> {
+    SPELLED.tokens(line)
+}
+
+pub fn read_calibration_number(line: &str) -> Option<u8> {
+    SPELLED.read_calibration_number(line)
+}
+
+/// Sums the calibration numbers of a document streamed line by line.
+pub fn read_document(doc: impl BufRead) -> Result<u64, ReadError> {
+    read_document_from(doc, &SPELLED)
+}
+
+pub fn read_document_with(doc: &str, scanner: &DigitScanner) -> Result<u32, ParseError> {
+    doc.lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            scanner
+                .read_calibration_number(line)
+                .map(|num| num as u32)
+                .ok_or_else(|| ParseError::at(idx, line, line, scanner.expected()))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("1abc2", 12)]
+    #[test_case("pqr3stu8vwx", 38)]
+    #[test_case("a1b2c3d4e5f", 15)]
+    #[test_case("treb7uchet", 77)]
+    fn test_read_cfg_line(line: &str, desired: u8) {
+        let res = read_calibration_number(line);
+
+        assert_eq!(res, Some(desired));
+    }
+
+    #[test_case("two1nine", 29)]
+    #[test_case("eightwothree", 83)]
+    #[test_case("abcone2threexyz", 13)]
+    #[test_case("xtwone3four", 24)]
+    #[test_case("4nineeightseven2", 42)]
+    #[test_case("zoneight234", 14)]
+    #[test_case("7pqrstsixteen", 76)]
+    #[test_case("eighthree", 83)]
+    #[test_case("sevenine", 79)]
+    fn test_read_with_spelled_digits(line: &str, desired: u8) {
+        let res = read_calibration_number(line);
+
+        assert_eq!(res, Some(desired));
+    }
+
+    #[test_case(
+        "1abc2
+        pqr3stu8vwx
+        a1b2c3d4e5f
+        treb7uchet"
+        => 142
+    )]
+    fn test_part1(doc: &str) -> u32 {
+        let input = Day01::parse(doc).unwrap();
+        Day01::part1(&input).unwrap()
+    }
+
+    #[test_case(
+        "two1nine
+        eightwothree
+        abcone2threexyz
+        xtwone3four
+        4nineeightseven2
+        zoneight234
+        7pqrstsixteen"
+        => 281
+    )]
+    fn test_part2(doc: &str) -> u32 {
+        let input = Day01::parse(doc).unwrap();
+        Day01::part2(&input).unwrap()
+    }
+
+    #[test_case("two1nine" => 12)]
+    #[test_case("xtwone3four" => 10)]
+    #[test_case("eightwothree" => 13)]
+    fn test_tokens_sum(line: &str) -> u32 {
+        tokens(line).map(|token| token.num.get_val() as u32).sum()
+    }
+
+    #[test_case("1abc2\nabc\ntreb7uchet", &SPELLED => ParseError::new(2, 1, "abc", "a digit or spelled number"))]
+    #[test_case("1abc2\n\none", &SPELLED => ParseError::new(2, 1, "", "a digit or spelled number"))]
+    #[test_case("1abc2\neightwothree", &DIGITS => ParseError::new(2, 1, "eightwothree", "a digit"))]
+    fn test_read_document_error(doc: &str, scanner: &DigitScanner) -> ParseError {
+        read_document_with(doc, scanner).unwrap_err()
+    }
+}
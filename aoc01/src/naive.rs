@@ -0,0 +1,35 @@
+//! The original calibration reader searching for every pattern separately,
+//! kept as a reference for tests and benchmarks.
+
+pub fn read_calibration_number(line: &str, numbers: &[&str]) -> Option<u8> {
+    // positions are byte offsets, so the digit has to be looked up by byte as well
+    let mut first = line
+        .find(|c: char| c.is_ascii_digit())
+        .map(|pos| (pos, line.as_bytes()[pos] - b'0'));
+
+    for (idx, val) in numbers.iter().enumerate() {
+        let pos = line.find(val);
+
+        if let Some(p) = pos {
+            if first.is_none() || p < first.unwrap().0 {
+                first = Some((p, (idx + 1) as u8))
+            }
+        }
+    }
+
+    let mut last = line
+        .rfind(|c: char| c.is_ascii_digit())
+        .map(|pos| (pos, line.as_bytes()[pos] - b'0'));
+
+    for (idx, val) in numbers.iter().enumerate() {
+        let pos = line.rfind(val);
+
+        if let Some(p) = pos {
+            if last.is_none() || p > last.unwrap().0 {
+                last = Some((p, (idx + 1) as u8))
+            }
+        }
+    }
+
+    Some(first?.1 * 10 + last?.1)
+}
@@ -0,0 +1,133 @@
+use aoc_core::ParseError;
+use rayon::prelude::*;
+
+use crate::{DigitScanner, MissingDigits, Report};
+
+/// Chunks smaller than this are not worth a task of their own.
+const MIN_CHUNK: usize = 1 << 16;
+
+/// Splits `doc` into chunks of about `size` bytes, each ending after a line break.
+fn chunks(doc: &str, size: usize) -> Vec<&str> {
+    let mut chunks = vec![];
+    let mut rest = doc;
+
+    while rest.len() > size {
+        // bytes, as the cut may fall inside a character while `\n` never does
+        let end = match rest.as_bytes()[size..].iter().position(|b| *b == b'\n') {
+            Some(pos) => size + pos + 1,
+            None => rest.len(),
+        };
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+
+    chunks
+}
+
+/// Sums the calibration numbers like [`read_document_with`](crate::read_document_with),
+/// spreading the lines over the rayon thread pool.
+///
+/// Reports the same error as the sequential reader if several lines are invalid.
+pub fn read_document_par(doc: &str, scanner: &DigitScanner) -> Result<u64, ParseError> {
+    read_document_report_par(doc, scanner, MissingDigits::Error).map(|report| report.total)
+}
+
+/// Like [`read_document_par`], handling lines without digits according to `missing`.
+pub fn read_document_report_par(
+    doc: &str,
+    scanner: &DigitScanner,
+    missing: MissingDigits,
+) -> Result<Report<u64>, ParseError> {
+    let size = (doc.len() / (rayon::current_num_threads() * 4)).max(MIN_CHUNK);
+
+    let results: Vec<_> = chunks(doc, size)
+        .into_par_iter()
+        .map(|chunk| {
+            let mut report = Report::new(0);
+            let mut lines = 0;
+            for (idx, line) in chunk.lines().enumerate() {
+                let total = scanner
+                    .read_calibration_number(line)
+                    .map(|num| report.total + num as u64);
+                report.push(idx, line, total, missing, scanner.expected())?;
+                lines += 1;
+            }
+
+            Ok((report, lines))
+        })
+        .collect();
+
+    // line numbers are relative to the chunk, shift them behind the previous chunks
+    let mut report = Report::new(0);
+    let mut offset = 0;
+    for result in results {
+        let (chunk, lines) = result.map_err(|mut err: ParseError| {
+            err.line += offset;
+            err
+        })?;
+        report.total += chunk.total;
+        report.lines += chunk.lines;
+        report
+            .missing
+            .extend(chunk.missing.iter().map(|line| line + offset));
+        offset += lines;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    use crate::{read_document_with, DIGITS, SPELLED};
+
+    #[test_case("a1\nb2\nc3\n", 3 => vec!["a1\nb2\n", "c3\n"])]
+    #[test_case("a1\nb2\nc3", 1 => vec!["a1\n", "b2\n", "c3"])]
+    #[test_case("a1\nb2\nc3", 100 => vec!["a1\nb2\nc3"])]
+    #[test_case("", 1 => Vec::<&str>::new())]
+    #[test_case("é1\nü2\n", 1 => vec!["é1\n", "ü2\n"])]
+    fn test_chunks(doc: &str, size: usize) -> Vec<&str> {
+        chunks(doc, size)
+    }
+
+    #[test]
+    fn test_matches_sequential() {
+        let doc = include_str!("../input.txt").repeat(100);
+
+        for scanner in [&*SPELLED, &*DIGITS] {
+            let sequential = read_document_with(&doc, scanner).unwrap();
+            assert_eq!(read_document_par(&doc, scanner), Ok(sequential as u64));
+        }
+    }
+
+    #[test]
+    fn test_first_error() {
+        let input = include_str!("../input.txt");
+        let lines = input.lines().count();
+        let doc = format!("{}abc\n{}def\n", input.repeat(50), input.repeat(50));
+
+        let err = read_document_par(&doc, &SPELLED).unwrap_err();
+        assert_eq!(err, read_document_with(&doc, &SPELLED).unwrap_err());
+        assert_eq!(err.line, 50 * lines + 1);
+    }
+
+    #[test_case(MissingDigits::Skip)]
+    #[test_case(MissingDigits::Zero)]
+    fn test_report_matches_sequential(missing: MissingDigits) {
+        let input = include_str!("../input.txt");
+        let doc = format!("{}abc\n{}\n{}", input.repeat(50), input.repeat(50), input);
+
+        let sequential = crate::read_document_report_from(doc.as_bytes(), &SPELLED, missing);
+        assert_eq!(
+            read_document_report_par(&doc, &SPELLED, missing),
+            Ok(sequential.unwrap())
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Property tests planting known numbers into random noise.
+
+use std::cmp::Reverse;
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::{
+    explain,
+    generator::{word, OVERLAPS},
+    naive, read_calibration_number, tokens, DigitScanner, DigitVocabulary, NUMBERS, SPELLED,
+};
+
+/// Noise that can not spell a number, not even combined with its neighbours.
+fn noise() -> impl Strategy<Value = String> {
+    "[abcdjklmpqyzäß€ ]{0,6}"
+}
+
+/// A digit, either written as such or spelled out unless it is zero.
+fn number(val: u8) -> impl Strategy<Value = String> {
+    any::<bool>().prop_map(move |spelled| match val {
+        1..=9 if spelled => word(val).to_owned(),
+        _ => val.to_string(),
+    })
+}
+
+/// Writes `first` followed by a word sharing its last letter, like `oneight`.
+fn overlapping_first(first: u8) -> Option<String> {
+    let (_, next) = OVERLAPS.iter().find(|(word, _)| *word == first)?;
+
+    Some(format!("{}{}", word(first), &word(*next)[1..]))
+}
+
+/// Writes `last` preceded by a word sharing its first letter, like `twone`.
+fn overlapping_last(last: u8) -> Option<String> {
+    let (prev, _) = OVERLAPS.iter().find(|(_, word)| *word == last)?;
+    let prev = word(*prev);
+
+    Some(format!("{}{}", &prev[..prev.len() - 1], word(last)))
+}
+
+/// Checks the first and last token against the scanner's own first and last number.
+fn tokens_agree(scanner: &DigitScanner, line: &str) -> Result<(), TestCaseError> {
+    let tokens: Vec<_> = scanner.tokens(line).collect();
+    let last = tokens
+        .iter()
+        .max_by_key(|token| (token.span.end, Reverse(token.span.start)));
+
+    prop_assert_eq!(tokens.first().map(|token| token.num), scanner.first(line));
+    prop_assert_eq!(last.map(|token| token.num), scanner.last(line));
+    for token in &tokens {
+        prop_assert!(line.get(token.span.clone()).is_some());
+    }
+
+    Ok(())
+}
+
+prop_compose! {
+    /// A line with the planted first and last number and up to three numbers between.
+    fn planted()(first in 1..=9u8, last in 1..=9u8, middle in vec(0..=9u8, 0..=3))(
+        first in number(first),
+        last in number(last),
+        middle in middle.into_iter().map(number).collect::<Vec<_>>(),
+        noise in vec(noise(), 6),
+        overlap in any::<(bool, bool)>(),
+        val in Just((first, last)),
+    ) -> (String, u8, u8) {
+        let first = match overlap.0 {
+            true => overlapping_first(val.0).unwrap_or(first),
+            false => first,
+        };
+        let last = match overlap.1 {
+            true => overlapping_last(val.1).unwrap_or(last),
+            false => last,
+        };
+
+        let mut line = noise[0].clone();
+        line.push_str(&first);
+        for (num, noise) in middle.iter().zip(&noise[1..]) {
+            line.push_str(noise);
+            line.push_str(num);
+        }
+        line.push_str(&noise[4]);
+        line.push_str(&last);
+        line.push_str(&noise[5]);
+
+        (line, val.0, val.1)
+    }
+}
+
+proptest! {
+    #[test]
+    fn prop_recovers_planted((line, first, last) in planted()) {
+        prop_assert_eq!(read_calibration_number(&line), Some(first * 10 + last));
+        prop_assert_eq!(SPELLED.first(&line).map(|num| num.get_val()), Some(first));
+        prop_assert_eq!(SPELLED.last(&line).map(|num| num.get_val()), Some(last));
+    }
+
+    #[test]
+    fn prop_recovers_single(before in noise(), num in (1..=9u8).prop_flat_map(|val| (Just(val), number(val))), after in noise()) {
+        let line = format!("{}{}{}", before, num.1, after);
+
+        prop_assert_eq!(read_calibration_number(&line), Some(num.0 * 11));
+    }
+
+    #[test]
+    fn prop_matches_naive(line in "[a-z0-9é€]{0,24}") {
+        prop_assert_eq!(read_calibration_number(&line), naive::read_calibration_number(&line, &NUMBERS));
+    }
+
+    #[test]
+    fn prop_tokens_agree(line in "[efghinorstuvwx1-9]{0,24}") {
+        tokens_agree(&SPELLED, &line)?;
+    }
+
+    #[test]
+    fn prop_tokens_agree_nested(line in "[ivx1-9]{0,24}") {
+        tokens_agree(&DigitScanner::new(&DigitVocabulary::roman()), &line)?;
+    }
+
+    #[test]
+    fn prop_no_panic(doc in any::<String>()) {
+        let _ = explain(&doc, &SPELLED);
+        let _ = doc.parse::<DigitVocabulary>();
+        for line in doc.lines() {
+            let _ = tokens(line).count();
+        }
+    }
+}
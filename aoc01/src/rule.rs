@@ -0,0 +1,323 @@
+use std::cmp::Reverse;
+
+use aoc_core::ParseError;
+
+use crate::{DigitScanner, Token};
+
+/// Turns the tokens of a line into its calibration value.
+///
+/// Rules only see lines with at least one token, ordered as by [`DigitScanner::tokens`].
+/// They return `None` if the value does not fit into a `u128`.
+pub trait CalibrationRule {
+    fn evaluate(&self, tokens: &[Token]) -> Option<u128>;
+}
+
+impl<F: Fn(&[Token]) -> Option<u128>> CalibrationRule for F {
+    fn evaluate(&self, tokens: &[Token]) -> Option<u128> {
+        self(tokens)
+    }
+}
+
+/// The first and the last digit as a two digit number, as in the puzzle.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FirstLast;
+
+/// The sum over all numbers made of `n` consecutive digits.
+///
+/// Lines with less than `n` digits are worth nothing.
+///
+/// # Panics
+///
+/// If `n` is zero.
+#[derive(Debug, Clone, Copy)]
+pub struct Windows(pub usize);
+
+/// All digits of the line read as a single number.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Concat;
+
+/// The sum of the digits weighted by their 1-based position.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Checksum;
+
+fn digits(tokens: &[Token]) -> Vec<u128> {
+    tokens
+        .iter()
+        .map(|token| token.num.get_val() as u128)
+        .collect()
+}
+
+fn concat(digits: &[u128]) -> Option<u128> {
+    digits
+        .iter()
+        .try_fold(0u128, |acc, digit| acc.checked_mul(10)?.checked_add(*digit))
+}
+
+impl CalibrationRule for FirstLast {
+    fn evaluate(&self, tokens: &[Token]) -> Option<u128> {
+        let first = tokens.first()?;
+        // the longest of the words ending last, like `DigitScanner::last`
+        let last = tokens
+            .iter()
+            .max_by_key(|token| (token.span.end, Reverse(token.span.start)))?;
+
+        Some(first.num.get_val() as u128 * 10 + last.num.get_val() as u128)
+    }
+}
+
+impl CalibrationRule for Windows {
+    fn evaluate(&self, tokens: &[Token]) -> Option<u128> {
+        digits(tokens)
+            .windows(self.0)
+            .try_fold(0u128, |acc, window| acc.checked_add(concat(window)?))
+    }
+}
+
+impl CalibrationRule for Concat {
+    fn evaluate(&self, tokens: &[Token]) -> Option<u128> {
+        concat(&digits(tokens))
+    }
+}
+
+impl CalibrationRule for Checksum {
+    fn evaluate(&self, tokens: &[Token]) -> Option<u128> {
+        digits(tokens)
+            .into_iter()
+            .zip(1..)
+            .try_fold(0u128, |acc, (digit, weight)| {
+                acc.checked_add(digit.checked_mul(weight)?)
+            })
+    }
+}
+
+/// What to do with lines that contain no digit at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MissingDigits {
+    /// Fail with the position of the line
+    #[default]
+    Error,
+    /// Leave the line out and list it in the report
+    Skip,
+    /// Count the line as worth zero and list it in the report
+    Zero,
+}
+
+/// The outcome of reading a document under a [`MissingDigits`] policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report<T> {
+    pub total: T,
+    /// Number of lines that contributed to the total
+    pub lines: usize,
+    /// 1-based numbers of the lines without digits
+    pub missing: Vec<usize>,
+}
+
+impl<T> Report<T> {
+    /// One warning per line without digits, for printing alongside the total.
+    pub fn warnings(&self) -> impl Iterator<Item = String> + '_ {
+        self.missing
+            .iter()
+            .map(|line| format!("line {}: no digit found", line))
+    }
+}
+
+impl<T> Report<T> {
+    pub(crate) fn new(total: T) -> Self {
+        Self {
+            total,
+            lines: 0,
+            missing: vec![],
+        }
+    }
+
+    /// Counts the line at 0-based `idx`, given the total including it or `None` if it
+    /// has no digits. Fails with `expected` for lines without digits under
+    /// [`MissingDigits::Error`].
+    pub(crate) fn push(
+        &mut self,
+        idx: usize,
+        line: &str,
+        total: Option<T>,
+        missing: MissingDigits,
+        expected: &str,
+    ) -> Result<(), ParseError> {
+        match total {
+            Some(total) => {
+                self.total = total;
+                self.lines += 1;
+            }
+            None => {
+                match missing {
+                    MissingDigits::Error => return Err(ParseError::at(idx, line, line, expected)),
+                    MissingDigits::Skip => {}
+                    MissingDigits::Zero => self.lines += 1,
+                }
+                self.missing.push(idx + 1);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sums the values of all lines under `rule`.
+///
+/// Fails on the first line without digits or whose value pushes the total beyond `T`.
+pub fn read_document_by<T, R>(doc: &str, scanner: &DigitScanner, rule: &R) -> Result<T, ParseError>
+where
+    T: TryFrom<u128>,
+    R: CalibrationRule + ?Sized,
+{
+    read_document_report(doc, scanner, rule, MissingDigits::Error).map(|report| report.total)
+}
+
+/// Like [`read_document_by`], handling lines without digits according to `missing`.
+pub fn read_document_report<T, R>(
+    doc: &str,
+    scanner: &DigitScanner,
+    rule: &R,
+    missing: MissingDigits,
+) -> Result<Report<T>, ParseError>
+where
+    T: TryFrom<u128>,
+    R: CalibrationRule + ?Sized,
+{
+    let overflow = format!("a total fitting into {}", std::any::type_name::<T>());
+
+    let mut sum = 0u128;
+    let total = T::try_from(sum).map_err(|_| ParseError::new(1, 1, "", &overflow))?;
+    let mut report = Report::new(total);
+    for (idx, line) in doc.lines().enumerate() {
+        let tokens: Vec<_> = scanner.tokens(line).collect();
+        let total = match tokens.is_empty() {
+            true => None,
+            false => {
+                let err = || ParseError::at(idx, line, line, &overflow);
+                sum = rule
+                    .evaluate(&tokens)
+                    .and_then(|val| sum.checked_add(val))
+                    .ok_or_else(err)?;
+                Some(T::try_from(sum).map_err(|_| err())?)
+            }
+        };
+        report.push(idx, line, total, missing, scanner.expected())?;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    use crate::{DigitVocabulary, SPELLED};
+
+    fn evaluate(rule: &dyn CalibrationRule, line: &str) -> Option<u128> {
+        let tokens: Vec<_> = SPELLED.tokens(line).collect();
+
+        rule.evaluate(&tokens)
+    }
+
+    #[test_case("two1nine" => Some(29))]
+    #[test_case("eighthree" => Some(83))]
+    #[test_case("7" => Some(77))]
+    fn test_first_last(line: &str) -> Option<u128> {
+        evaluate(&FirstLast, line)
+    }
+
+    #[test_case(2, "1234" => Some(12 + 23 + 34))]
+    #[test_case(3, "twone3" => Some(213))]
+    #[test_case(3, "12" => Some(0))]
+    #[test_case(38, &"9".repeat(40) => Some(3 * (10u128.pow(38) - 1)))]
+    #[test_case(38, &"9".repeat(41) => None)]
+    #[test_case(39, &"9".repeat(39) => None)]
+    fn test_windows(len: usize, line: &str) -> Option<u128> {
+        evaluate(&Windows(len), line)
+    }
+
+    #[test_case("a1twone" => Some(121))]
+    #[test_case(&"9".repeat(38) => Some(10u128.pow(38) - 1))]
+    #[test_case(&"9".repeat(39) => None)]
+    fn test_concat(line: &str) -> Option<u128> {
+        evaluate(&Concat, line)
+    }
+
+    #[test_case("123" => Some(1 + 4 + 9))]
+    #[test_case("xtwone3four" => Some(2 + 2 + 9 + 16))]
+    fn test_checksum(line: &str) -> Option<u128> {
+        evaluate(&Checksum, line)
+    }
+
+    #[test_case("xviii" => Ok(88))]
+    #[test_case("iv.vi" => Ok(46))]
+    #[test_case("xii\nvii" => Ok(22 + 77))]
+    fn test_first_last_nested(doc: &str) -> Result<u32, ParseError> {
+        let scanner = DigitScanner::new(&DigitVocabulary::roman());
+
+        assert_eq!(
+            read_document_by::<u32, _>(doc, &scanner, &FirstLast),
+            crate::read_document_with(doc, &scanner)
+        );
+        read_document_by(doc, &scanner, &FirstLast)
+    }
+
+    #[test]
+    fn test_read_document_by() {
+        let doc = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four";
+
+        assert_eq!(
+            read_document_by::<u64, _>(doc, &SPELLED, &FirstLast),
+            Ok(29 + 83 + 13 + 24)
+        );
+        assert_eq!(
+            read_document_by::<u128, _>(doc, &SPELLED, &Concat),
+            Ok(219 + 823 + 123 + 2134)
+        );
+
+        let max_token =
+            |tokens: &[Token]| tokens.iter().map(|token| token.num.get_val() as u128).max();
+        assert_eq!(
+            read_document_by::<u8, _>(doc, &SPELLED, &max_token),
+            Ok(9 + 8 + 3 + 4)
+        );
+    }
+
+    #[test_case("1abc2\nabc" => ParseError::new(2, 1, "abc", "a digit or spelled number"))]
+    #[test_case("99\n99\n99" => ParseError::new(3, 1, "99", "a total fitting into u8"))]
+    fn test_read_document_by_error(doc: &str) -> ParseError {
+        read_document_by::<u8, _>(doc, &SPELLED, &FirstLast).unwrap_err()
+    }
+
+    #[test_case(MissingDigits::Skip => Report { total: 12 + 77, lines: 2, missing: vec![2, 4] })]
+    #[test_case(MissingDigits::Zero => Report { total: 12 + 77, lines: 4, missing: vec![2, 4] })]
+    fn test_read_document_report(missing: MissingDigits) -> Report<u32> {
+        let doc = "1abc2\nabc\ntreb7uchet\n\n";
+
+        read_document_report(doc, &SPELLED, &FirstLast, missing).unwrap()
+    }
+
+    #[test]
+    fn test_warnings() {
+        let report = Report {
+            total: 0,
+            lines: 1,
+            missing: vec![2, 5],
+        };
+
+        assert_eq!(
+            report.warnings().collect::<Vec<_>>(),
+            ["line 2: no digit found", "line 5: no digit found"]
+        );
+    }
+
+    #[test]
+    fn test_read_document_by_overflow() {
+        let doc = "9".repeat(40);
+        let scanner = DigitScanner::new(&DigitVocabulary::new());
+
+        let err = read_document_by::<u128, _>(&doc, &scanner, &Concat).unwrap_err();
+        assert_eq!(err.expected, "a total fitting into u128");
+    }
+}
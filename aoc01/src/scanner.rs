@@ -0,0 +1,411 @@
+use std::{cmp::Reverse, collections::VecDeque, iter};
+
+use crate::{DigitVocabulary, NumberPos, Token};
+
+const NONE: u32 = u32::MAX;
+
+#[derive(Debug, Clone, Copy)]
+struct Pattern {
+    len: usize,
+    val: u8,
+    spelled: bool,
+}
+
+/// Aho-Corasick automaton with a dense transition table over bytes.
+#[derive(Debug)]
+struct Automaton {
+    transitions: Vec<u32>,
+    depth: Vec<usize>,
+    /// Longest pattern that is a suffix of the input read so far
+    output: Vec<Option<Pattern>>,
+    /// Next state along the failure links whose own pattern is a shorter suffix
+    dict: Vec<u32>,
+}
+
+impl Automaton {
+    fn new(patterns: impl IntoIterator<Item = (Vec<u8>, Pattern)>) -> Self {
+        let mut automaton = Self {
+            transitions: vec![NONE; 256],
+            depth: vec![0],
+            output: vec![None],
+            dict: vec![NONE],
+        };
+
+        for (bytes, pattern) in patterns {
+            let mut state = 0;
+            for byte in bytes {
+                let idx = state * 256 + byte as usize;
+                if automaton.transitions[idx] == NONE {
+                    automaton.transitions[idx] = automaton.depth.len() as u32;
+                    automaton.transitions.extend([NONE; 256]);
+                    automaton.depth.push(automaton.depth[state] + 1);
+                    automaton.output.push(None);
+                    automaton.dict.push(NONE);
+                }
+                state = automaton.transitions[idx] as usize;
+            }
+            automaton.output[state] = Some(pattern);
+        }
+
+        // resolve failures breadth first, so every shorter suffix is complete already
+        let mut fail = vec![0; automaton.depth.len()];
+        let mut queue = VecDeque::new();
+        for byte in 0..256 {
+            match automaton.transitions[byte] {
+                NONE => automaton.transitions[byte] = 0,
+                next => queue.push_back(next as usize),
+            }
+        }
+
+        let mut own = vec![false; automaton.depth.len()];
+        while let Some(state) = queue.pop_front() {
+            own[state] = automaton.output[state].is_some();
+            automaton.dict[state] = match own[fail[state]] {
+                true => fail[state] as u32,
+                false => automaton.dict[fail[state]],
+            };
+            if !own[state] {
+                automaton.output[state] = automaton.output[fail[state]];
+            }
+
+            for byte in 0..256 {
+                let idx = state * 256 + byte;
+                let fallback = automaton.transitions[fail[state] * 256 + byte];
+                match automaton.transitions[idx] {
+                    NONE => automaton.transitions[idx] = fallback,
+                    next => {
+                        fail[next as usize] = fallback as usize;
+                        queue.push_back(next as usize);
+                    }
+                }
+            }
+        }
+
+        automaton
+    }
+
+    /// Finds the match starting first in `bytes`, preferring the longer one on ties.
+    /// Returns the offset of its start within `bytes`.
+    fn leftmost(
+        &self,
+        bytes: impl Iterator<Item = u8>,
+        ignore_case: bool,
+    ) -> Option<(usize, Pattern)> {
+        let mut state = 0;
+        let mut best: Option<(usize, Pattern)> = None;
+
+        for (pos, mut byte) in bytes.enumerate() {
+            if ignore_case {
+                byte = byte.to_ascii_lowercase();
+            }
+            state = self.transitions[state * 256 + byte as usize] as usize;
+
+            // every later match starts within the currently tracked prefix
+            if let Some((start, _)) = best {
+                if pos + 1 - self.depth[state] > start {
+                    break;
+                }
+            }
+
+            if let Some(pattern) = self.output[state] {
+                let start = pos + 1 - pattern.len;
+                let better = match best {
+                    Some((best_start, best)) => {
+                        start < best_start || (start == best_start && pattern.len > best.len)
+                    }
+                    None => true,
+                };
+                if better {
+                    best = Some((start, pattern));
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Finds every match in `bytes`, including overlapping ones, as offsets past their end.
+    fn matches<'a>(
+        &'a self,
+        bytes: impl Iterator<Item = u8> + 'a,
+        ignore_case: bool,
+    ) -> impl Iterator<Item = (usize, Pattern)> + 'a {
+        bytes
+            .enumerate()
+            .scan(0, move |state, (pos, mut byte)| {
+                if ignore_case {
+                    byte = byte.to_ascii_lowercase();
+                }
+                *state = self.transitions[*state * 256 + byte as usize] as usize;
+
+                Some((pos + 1, *state))
+            })
+            .flat_map(move |(end, state)| {
+                // states only inherit the pattern of their dictionary link
+                let mut next = state as u32;
+                iter::from_fn(move || {
+                    while next != NONE {
+                        let state = next as usize;
+                        next = self.dict[state];
+                        match self.output[state] {
+                            Some(pattern) if pattern.len == self.depth[state] => {
+                                return Some((end, pattern))
+                            }
+                            _ => {}
+                        }
+                    }
+                    None
+                })
+            })
+    }
+}
+
+/// Finds the first and last digit of a line in a single pass each.
+///
+/// Matches the digits and words of a [`DigitVocabulary`].
+/// Overlapping words like `eighthree` are recognised from both ends.
+#[derive(Debug)]
+pub struct DigitScanner {
+    forward: Automaton,
+    backward: Automaton,
+    spelled: bool,
+    ignore_case: bool,
+}
+
+impl DigitScanner {
+    pub fn new(vocabulary: &DigitVocabulary) -> Self {
+        let ignore_case = vocabulary.is_case_insensitive();
+        let patterns = || {
+            let digits = vocabulary.digits().map(|(digit, val)| {
+                let pattern = Pattern {
+                    len: digit.len_utf8(),
+                    val,
+                    spelled: false,
+                };
+                (digit.to_string().into_bytes(), pattern)
+            });
+            let words = vocabulary.words().map(move |(word, val)| {
+                let pattern = Pattern {
+                    len: word.len(),
+                    val,
+                    spelled: true,
+                };
+                let bytes = match ignore_case {
+                    true => word.to_ascii_lowercase().into_bytes(),
+                    false => word.as_bytes().to_vec(),
+                };
+                (bytes, pattern)
+            });
+
+            digits.chain(words)
+        };
+
+        Self {
+            forward: Automaton::new(patterns()),
+            backward: Automaton::new(patterns().map(|(mut bytes, pattern)| {
+                bytes.reverse();
+                (bytes, pattern)
+            })),
+            spelled: !vocabulary.is_empty(),
+            ignore_case,
+        }
+    }
+
+    /// Whether spelled numbers are recognised in addition to digits.
+    pub fn is_spelled(&self) -> bool {
+        self.spelled
+    }
+
+    /// What a line needs to contain, for errors about lines without any number.
+    pub fn expected(&self) -> &'static str {
+        if self.spelled {
+            "a digit or spelled number"
+        } else {
+            "a digit"
+        }
+    }
+
+    pub fn first(&self, line: &str) -> Option<NumberPos> {
+        let (start, pattern) = self.forward.leftmost(line.bytes(), self.ignore_case)?;
+
+        Some(pattern.at(start))
+    }
+
+    pub fn last(&self, line: &str) -> Option<NumberPos> {
+        let (start, pattern) = self
+            .backward
+            .leftmost(line.bytes().rev(), self.ignore_case)?;
+
+        Some(pattern.at(line.len() - start - pattern.len))
+    }
+
+    pub fn read_calibration_number(&self, line: &str) -> Option<u8> {
+        Some(self.first(line)?.get_val() * 10 + self.last(line)?.get_val())
+    }
+
+    /// Every digit and word in `line`, overlapping ones included.
+    ///
+    /// Tokens are ordered by their start, longer ones first, so the first token
+    /// agrees with [`Self::first`].
+    pub fn tokens(&self, line: &str) -> impl Iterator<Item = Token> {
+        let mut tokens: Vec<_> = self
+            .forward
+            .matches(line.bytes(), self.ignore_case)
+            .map(|(end, pattern)| Token {
+                span: end - pattern.len..end,
+                num: pattern.at(end - pattern.len),
+            })
+            .collect();
+        tokens.sort_by_key(|token| (token.span.start, Reverse(token.span.end)));
+
+        tokens.into_iter()
+    }
+}
+
+impl Pattern {
+    fn at(&self, pos: usize) -> NumberPos {
+        if self.spelled {
+            NumberPos::String(pos, self.val)
+        } else {
+            NumberPos::Digit(pos, self.val)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ops::Range;
+
+    use test_case::test_case;
+
+    use crate::{naive, NUMBERS};
+
+    #[test_case("a1b2c3" => Some((1, 1)))]
+    #[test_case("xtwone3four" => Some((1, 2)))]
+    #[test_case("eighthree" => Some((0, 8)))]
+    #[test_case("ninine" => Some((2, 9)))]
+    #[test_case("x0y" => Some((1, 0)))]
+    #[test_case("äöthree1" => Some((4, 3)))]
+    #[test_case("abc" => None)]
+    fn test_first(line: &str) -> Option<(usize, u8)> {
+        let scanner = DigitScanner::new(&DigitVocabulary::english());
+
+        scanner
+            .first(line)
+            .map(|num| (num.get_pos(), num.get_val()))
+    }
+
+    #[test_case("a1b2c3" => Some((5, 3)))]
+    #[test_case("xtwone3four" => Some((7, 4)))]
+    #[test_case("eighthree" => Some((4, 3)))]
+    #[test_case("twone" => Some((2, 1)))]
+    #[test_case("1éeight€" => Some((3, 8)))]
+    #[test_case("abc" => None)]
+    fn test_last(line: &str) -> Option<(usize, u8)> {
+        let scanner = DigitScanner::new(&DigitVocabulary::english());
+
+        scanner.last(line).map(|num| (num.get_pos(), num.get_val()))
+    }
+
+    #[test_case(&["a", "ab", "b"], "xab" => Some((1, 2)))]
+    #[test_case(&["bc", "abcd"], "abcd" => Some((0, 2)))]
+    fn test_first_prefers_longest(words: &[&str], line: &str) -> Option<(usize, u8)> {
+        let scanner = DigitScanner::new(&DigitVocabulary::from_words(words));
+
+        scanner
+            .first(line)
+            .map(|num| (num.get_pos(), num.get_val()))
+    }
+
+    #[test_case("twone" => vec![(0..3, 2), (2..5, 1)])]
+    #[test_case("a1eightwo3" => vec![(1..2, 1), (2..7, 8), (6..9, 2), (9..10, 3)])]
+    #[test_case("oneightwone" => vec![(0..3, 1), (2..7, 8), (6..9, 2), (8..11, 1)])]
+    #[test_case("abc" => Vec::<(Range<usize>, u8)>::new())]
+    fn test_tokens(line: &str) -> Vec<(Range<usize>, u8)> {
+        let scanner = DigitScanner::new(&DigitVocabulary::english());
+
+        scanner
+            .tokens(line)
+            .map(|token| (token.span, token.num.get_val()))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokens_nested() {
+        let scanner = DigitScanner::new(&DigitVocabulary::roman().ignore_case(true));
+
+        let tokens: Vec<_> = scanner.tokens("xVIII").map(|token| token.span).collect();
+        assert_eq!(
+            tokens,
+            [1..5, 1..4, 1..3, 1..2, 2..5, 2..4, 2..3, 3..5, 3..4, 4..5]
+        );
+    }
+
+    #[test_case(DigitVocabulary::english(), include_str!("../input.txt"))]
+    #[test_case(DigitVocabulary::roman(), "xviii\niv.vi\nviiix\niiii\nxvx")]
+    fn test_tokens_first_last(vocabulary: DigitVocabulary, doc: &str) {
+        let scanner = DigitScanner::new(&vocabulary);
+
+        for line in doc.lines() {
+            let tokens: Vec<_> = scanner.tokens(line).collect();
+            let last = tokens
+                .iter()
+                .max_by_key(|token| (token.span.end, Reverse(token.span.start)));
+
+            assert_eq!(tokens.first().map(|token| token.num), scanner.first(line));
+            assert_eq!(last.map(|token| token.num), scanner.last(line), "{}", line);
+        }
+    }
+
+    #[test_case("x\u{ff13}y" => Some((1, 3)))]
+    #[test_case("ab٣" => Some((2, 3)))]
+    #[test_case("éfour\u{ff11}" => Some((2, 4)))]
+    fn test_first_unicode(line: &str) -> Option<(usize, u8)> {
+        let vocabulary = DigitVocabulary::english().unicode_digits(true);
+        let scanner = DigitScanner::new(&vocabulary);
+
+        scanner
+            .first(line)
+            .map(|num| (num.get_pos(), num.get_val()))
+    }
+
+    #[test_case("x\u{ff13}y" => Some((1..4, 3)))]
+    #[test_case("\u{ff11}٢🙂three" => Some((9..14, 3)))]
+    #[test_case("éfour\u{ff11}z" => Some((6..9, 1)))]
+    fn test_last_unicode(line: &str) -> Option<(Range<usize>, u8)> {
+        let vocabulary = DigitVocabulary::english().unicode_digits(true);
+        let scanner = DigitScanner::new(&vocabulary);
+        let last = scanner.last(line)?;
+        let token = scanner
+            .tokens(line)
+            .find(|token| token.span.start == last.get_pos())?;
+
+        // spans always fall on character boundaries
+        assert!(line.get(token.span.clone()).is_some());
+        Some((token.span, last.get_val()))
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let scanner = DigitScanner::new(&DigitVocabulary::english());
+        let digits = DigitScanner::new(&DigitVocabulary::new());
+        let unicode = ["é1two", "ü€eightwo7ß", "three🙂", "ａ１nine"];
+
+        for line in include_str!("../input.txt").lines().chain(unicode) {
+            assert_eq!(
+                scanner.read_calibration_number(line),
+                naive::read_calibration_number(line, &NUMBERS),
+                "{}",
+                line
+            );
+            assert_eq!(
+                digits.read_calibration_number(line),
+                naive::read_calibration_number(line, &[]),
+                "{}",
+                line
+            );
+        }
+    }
+}
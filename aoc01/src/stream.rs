@@ -0,0 +1,179 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    io::{self, BufRead},
+};
+
+use aoc_core::ParseError;
+
+use crate::{DigitScanner, MissingDigits, Report};
+
+/// Failure while reading a document from a stream.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "reading failed: {}", err),
+            Self::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+/// Sums the calibration numbers of a streamed document, one line at a time.
+///
+/// Only the current line is held in memory. Lines end in `\n` or `\r\n`.
+/// The total is a `u64`, so documents with billions of lines still fit.
+pub fn read_document_from(reader: impl BufRead, scanner: &DigitScanner) -> Result<u64, ReadError> {
+    read_document_report_from(reader, scanner, MissingDigits::Error).map(|report| report.total)
+}
+
+/// Like [`read_document_from`], handling lines without digits according to `missing`.
+pub fn read_document_report_from(
+    mut reader: impl BufRead,
+    scanner: &DigitScanner,
+    missing: MissingDigits,
+) -> Result<Report<u64>, ReadError> {
+    let mut report = Report::new(0);
+    let mut buf = String::new();
+    for idx in 0.. {
+        buf.clear();
+        if reader.read_line(&mut buf)? == 0 {
+            break;
+        }
+
+        let line = buf.strip_suffix('\n').unwrap_or(&buf);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let total = scanner
+            .read_calibration_number(line)
+            .map(|num| report.total + num as u64);
+        report.push(idx, line, total, missing, scanner.expected())?;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufReader, Read};
+
+    use test_case::test_case;
+
+    use crate::{read_document, read_document_with, DIGITS, SPELLED};
+
+    /// Produces `lines` copies of a line without ever holding more than one.
+    struct Repeat {
+        line: &'static [u8],
+        lines: usize,
+        offset: usize,
+    }
+
+    impl Read for Repeat {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.lines == 0 {
+                return Ok(0);
+            }
+
+            let rest = &self.line[self.offset..];
+            let len = rest.len().min(buf.len());
+            buf[..len].copy_from_slice(&rest[..len]);
+            self.offset += len;
+            if self.offset == self.line.len() {
+                self.offset = 0;
+                self.lines -= 1;
+            }
+
+            Ok(len)
+        }
+    }
+
+    #[test_case("two1nine\neightwothree\n" => 112)]
+    #[test_case("two1nine\r\neightwothree\r\n" => 112)]
+    #[test_case("two1nine\neightwothree" => 112)]
+    #[test_case("" => 0)]
+    fn test_read_document(doc: &str) -> u64 {
+        read_document(doc.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_matches_in_memory() {
+        let doc = include_str!("../input.txt");
+
+        for scanner in [&*SPELLED, &*DIGITS] {
+            let streamed = read_document_from(BufReader::with_capacity(7, doc.as_bytes()), scanner);
+            assert_eq!(
+                streamed.unwrap(),
+                read_document_with(doc, scanner).unwrap() as u64
+            );
+        }
+    }
+
+    #[test]
+    fn test_repeated() {
+        let reader = Repeat {
+            line: b"xtwone3four\n",
+            lines: 100_000,
+            offset: 0,
+        };
+
+        assert_eq!(read_document(BufReader::new(reader)).unwrap(), 2_400_000);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = read_document("1abc2\r\nabc\r\n".as_bytes()).unwrap_err();
+
+        match err {
+            ReadError::Parse(err) => {
+                assert_eq!(
+                    err,
+                    ParseError::new(2, 1, "abc", "a digit or spelled number")
+                )
+            }
+            ReadError::Io(err) => panic!("unexpected {}", err),
+        }
+    }
+
+    #[test_case(MissingDigits::Skip => (12 + 77, 2, vec![2, 4]))]
+    #[test_case(MissingDigits::Zero => (12 + 77, 4, vec![2, 4]))]
+    fn test_read_document_report(missing: MissingDigits) -> (u64, usize, Vec<usize>) {
+        let doc = "1abc2\r\nabc\ntreb7uchet\n\n";
+
+        let report = read_document_report_from(doc.as_bytes(), &SPELLED, missing).unwrap();
+        (report.total, report.lines, report.missing)
+    }
+
+    #[test]
+    fn test_io_error() {
+        let err = read_document(&b"1abc2\n\xff\n"[..]).unwrap_err();
+
+        assert!(matches!(err, ReadError::Io(_)), "{}", err);
+    }
+}
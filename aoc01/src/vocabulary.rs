@@ -0,0 +1,243 @@
+use std::{fs, io, iter, path::Path, str::FromStr};
+
+use aoc_core::ParseError;
+
+use crate::NUMBERS;
+
+/// The zeros of the Unicode decimal digit ranges besides ASCII, each followed by one to nine.
+const UNICODE_ZEROS: [u32; 67] = [
+    0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6, 0x0D66,
+    0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90,
+    0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10,
+    0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0, 0x11650,
+    0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16A60, 0x16AC0,
+    0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E950,
+    0x1FBF0,
+];
+
+/// Words that stand for a digit in a calibration line, on top of the ASCII digits.
+///
+/// Vocabularies are either built in code or parsed from a list of `word = value` lines,
+/// where `#` starts a comment and a line reading `@ignore-case` makes the words match
+/// regardless of their ASCII case. `@unicode-digits` enables [`Self::unicode_digits`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DigitVocabulary {
+    words: Vec<(String, u8)>,
+    ignore_case: bool,
+    unicode_digits: bool,
+}
+
+impl DigitVocabulary {
+    /// A vocabulary without words, so only digits are recognised.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn english() -> Self {
+        Self::from_words(&NUMBERS)
+    }
+
+    pub fn german() -> Self {
+        Self::from_words(&[
+            "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+        ])
+    }
+
+    pub fn french() -> Self {
+        Self::from_words(&[
+            "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+        ])
+    }
+
+    /// Lowercase roman numerals, longer numerals win over the ones they start with.
+    pub fn roman() -> Self {
+        Self::from_words(&["i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix"])
+    }
+
+    /// Maps the word at index `i` to the digit `i + 1`.
+    pub fn from_words(words: &[&str]) -> Self {
+        words
+            .iter()
+            .zip(1..)
+            .fold(Self::new(), |vocabulary, (word, val)| {
+                vocabulary.with_word(*word, val)
+            })
+    }
+
+    /// Adds `word` standing for the digit `val`.
+    ///
+    /// # Panics
+    ///
+    /// If `val` is no single digit or `word` is empty.
+    pub fn with_word(mut self, word: impl Into<String>, val: u8) -> Self {
+        let word = word.into();
+        assert!(val <= 9, "{} is no single digit", val);
+        assert!(!word.is_empty(), "empty words can not be matched");
+
+        self.words.push((word, val));
+        self
+    }
+
+    /// Matches the words regardless of their ASCII case.
+    pub fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    pub fn is_case_insensitive(&self) -> bool {
+        self.ignore_case
+    }
+
+    /// Recognises the decimal digits of all scripts, like fullwidth `３` or Arabic-Indic `٣`,
+    /// instead of only the ASCII ones.
+    pub fn unicode_digits(mut self, unicode_digits: bool) -> Self {
+        self.unicode_digits = unicode_digits;
+        self
+    }
+
+    pub fn has_unicode_digits(&self) -> bool {
+        self.unicode_digits
+    }
+
+    /// The characters counting as digits, with their value.
+    pub(crate) fn digits(&self) -> impl Iterator<Item = (char, u8)> {
+        let zeros = match self.unicode_digits {
+            true => &UNICODE_ZEROS[..],
+            false => &[],
+        };
+
+        iter::once('0' as u32)
+            .chain(zeros.iter().copied())
+            .flat_map(|zero| (0..=9).map(move |val| (zero + val as u32, val)))
+            .map(|(code, val)| (char::from_u32(code).unwrap(), val))
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u8)> {
+        self.words.iter().map(|(word, val)| (word.as_str(), *val))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+impl FromStr for DigitVocabulary {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut vocabulary = Self::new();
+
+        for (idx, raw_line) in s.lines().enumerate() {
+            let err =
+                |snippet: &str, expected: &str| ParseError::at(idx, raw_line, snippet, expected);
+
+            let line = raw_line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            match line {
+                "@ignore-case" => {
+                    vocabulary.ignore_case = true;
+                    continue;
+                }
+                "@unicode-digits" => {
+                    vocabulary.unicode_digits = true;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let (word, val) = line
+                .split_once('=')
+                .ok_or_else(|| err(&line[line.len()..], "`=`"))?;
+            let (word, val) = (word.trim(), val.trim());
+            if word.is_empty() {
+                return Err(err(word, "a word"));
+            }
+            let val = val
+                .parse()
+                .ok()
+                .filter(|val| *val <= 9)
+                .ok_or_else(|| err(val, "a digit from 0 to 9"))?;
+
+            vocabulary.words.push((word.to_owned(), val));
+        }
+
+        Ok(vocabulary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    use crate::DigitScanner;
+
+    #[test_case(DigitVocabulary::english(), "xtwone3four" => Some(24))]
+    #[test_case(DigitVocabulary::german(), "xzweidreix" => Some(23))]
+    #[test_case(DigitVocabulary::german(), "fünfundzwanzig" => Some(55))]
+    #[test_case(DigitVocabulary::french(), "huitrois" => Some(83))]
+    #[test_case(DigitVocabulary::roman(), "aviiib" => Some(88))]
+    #[test_case(DigitVocabulary::roman(), "xixiv" => Some(94))]
+    #[test_case(DigitVocabulary::english().with_word("zero", 0), "zeroone" => Some(1))]
+    #[test_case(DigitVocabulary::english(), "TwoNine" => None)]
+    #[test_case(DigitVocabulary::english().ignore_case(true), "TwoNINE" => Some(29))]
+    #[test_case(DigitVocabulary::new(), "two1nine" => Some(11))]
+    #[test_case(DigitVocabulary::new(), "x\u{ff13}y٤z" => None)]
+    #[test_case(DigitVocabulary::new().unicode_digits(true), "x\u{ff13}y٤z" => Some(34))]
+    #[test_case(DigitVocabulary::german().unicode_digits(true), "fünf۷" => Some(57))]
+    fn test_vocabulary(vocabulary: DigitVocabulary, line: &str) -> Option<u8> {
+        DigitScanner::new(&vocabulary).read_calibration_number(line)
+    }
+
+    #[test]
+    fn test_unicode_digits() {
+        let digits: Vec<_> = DigitVocabulary::new()
+            .unicode_digits(true)
+            .digits()
+            .collect();
+
+        assert_eq!(digits.len(), 680);
+        for (digit, val) in digits {
+            assert!(digit.is_numeric(), "{:?}", digit);
+            assert_eq!(digit.to_digit(10).unwrap_or(val as u32), val as u32);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let vocabulary: DigitVocabulary = "# mixed case words
+            @ignore-case
+            @unicode-digits
+            null = 0
+            Eins = 1  # trailing comment
+
+            zwei=2"
+            .parse()
+            .unwrap();
+
+        let desired = DigitVocabulary::new()
+            .with_word("null", 0)
+            .with_word("Eins", 1)
+            .with_word("zwei", 2)
+            .ignore_case(true)
+            .unicode_digits(true);
+        assert_eq!(vocabulary, desired);
+    }
+
+    #[test_case("eins 1" => ParseError::new(1, 7, "", "`=`"))]
+    #[test_case("eins = 1\n = 2" => ParseError::new(2, 2, "", "a word"))]
+    #[test_case("eins = zehn" => ParseError::new(1, 8, "zehn", "a digit from 0 to 9"))]
+    #[test_case("zehn = 10" => ParseError::new(1, 8, "10", "a digit from 0 to 9"))]
+    fn test_parse_error(input: &str) -> ParseError {
+        input.parse::<DigitVocabulary>().unwrap_err()
+    }
+}
@@ -0,0 +1,267 @@
+use std::{collections::BTreeMap, fs, io, ops::Range, path::Path};
+
+use aoc_core::ParseError;
+
+use crate::{is_colour, Game, Round};
+
+/// The cubes in the bag, by colour, that the games are checked against.
+///
+/// Colours that are not listed are not in the bag at all.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Bag {
+    limits: BTreeMap<String, u64>,
+}
+
+impl Bag {
+    /// An empty bag.
+    pub fn new() -> Self {
+        Self {
+            limits: BTreeMap::new(),
+        }
+    }
+
+    pub fn with(mut self, colour: impl Into<String>, limit: u64) -> Self {
+        self.limits.insert(colour.into(), limit);
+        self
+    }
+
+    /// Number of cubes of `colour` in the bag.
+    pub fn get(&self, colour: &str) -> u64 {
+        self.limits.get(colour).copied().unwrap_or_default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.limits
+            .iter()
+            .map(|(colour, limit)| (colour.as_str(), *limit))
+    }
+
+    pub fn total(&self) -> u64 {
+        self.limits.values().sum()
+    }
+
+    /// Whether `round` can be drawn from the bag.
+    pub fn contains(&self, round: &Round) -> bool {
+        round
+            .iter()
+            .all(|(colour, count)| count <= self.get(colour))
+    }
+
+    /// Parses comma separated `colour=count` pairs, as given on the command line.
+    pub fn from_flags(flags: &str) -> Result<Self, ParseError> {
+        let err = |snippet: &str, expected: &str| ParseError::at(0, flags, snippet, expected);
+
+        let mut bag = Self::new();
+        for entry in flags.split(',') {
+            let entry = entry.trim();
+            let (colour, limit) = entry
+                .split_once('=')
+                .ok_or_else(|| err(entry, "`<colour>=<count>`"))?;
+            let (colour, limit) = (colour.trim(), limit.trim());
+            if !is_colour(colour) {
+                return Err(err(colour, "a colour name"));
+            }
+            let limit = limit.parse().map_err(|_| err(limit, "a cube count"))?;
+
+            bag = bag.with(colour, limit);
+        }
+
+        Ok(bag)
+    }
+
+    /// Parses a TOML table of colours to cube counts, like `red = 12`.
+    pub fn from_toml(input: &str) -> Result<Self, ParseError> {
+        let limits = toml::from_str(input).map_err(|err| {
+            let span = err.span().unwrap_or(0..0);
+            error_at(input, span, "a table of colours to cube counts")
+        })?;
+
+        Self::from_limits(input, limits)
+    }
+
+    /// Parses a JSON object of colours to cube counts, like `{"red": 12}`.
+    pub fn from_json(input: &str) -> Result<Self, ParseError> {
+        let limits = serde_json::from_str(input).map_err(|err| {
+            // the column counts bytes and points into the offending token
+            let line_start: usize = input
+                .split_inclusive('\n')
+                .take(err.line().saturating_sub(1))
+                .map(str::len)
+                .sum();
+            let offset = (line_start + err.column().saturating_sub(1)).min(input.len());
+
+            error_at(
+                input,
+                token(input, offset),
+                "an object of colours to cube counts",
+            )
+        })?;
+
+        Self::from_limits(input, limits)
+    }
+
+    /// Checks the colour names read from `input` like [`parse_input`](crate::parse_input).
+    fn from_limits(input: &str, limits: BTreeMap<String, u64>) -> Result<Self, ParseError> {
+        let Some(colour) = limits.keys().find(|colour| !is_colour(colour)) else {
+            return Ok(Self { limits });
+        };
+
+        // quoted unless it is a bare TOML key, escaped names are not found at all
+        let start = match input.find(&format!("\"{}\"", colour)) {
+            Some(pos) => pos + 1,
+            None => input.find(colour.as_str()).unwrap_or(0),
+        };
+        let end = match input[start..].starts_with(colour.as_str()) {
+            true => start + colour.len(),
+            false => start,
+        };
+
+        Err(error_at(input, start..end, "a colour name"))
+    }
+
+    /// Loads a bag from a `.json` file, or from TOML for any other extension.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let input = fs::read_to_string(path)?;
+
+        let bag = match path.extension().is_some_and(|ext| ext == "json") {
+            true => Self::from_json(&input),
+            false => Self::from_toml(&input),
+        };
+        bag.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+impl Default for Bag {
+    /// 12 red, 13 green and 14 blue cubes.
+    fn default() -> Self {
+        Self::new()
+            .with("red", 12)
+            .with("green", 13)
+            .with("blue", 14)
+    }
+}
+
+/// The token of `input` around the byte `offset`, up to whitespace or punctuation.
+fn token(input: &str, offset: usize) -> Range<usize> {
+    let is_token = |c: char| !c.is_whitespace() && !"{}[],:".contains(c);
+
+    let mut offset = offset;
+    while !input.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let start = input[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_token(*c))
+        .last()
+        .map_or(offset, |(pos, _)| pos);
+    let end = input[offset..]
+        .find(|c| !is_token(c))
+        .map_or(input.len(), |pos| offset + pos);
+
+    start..end
+}
+
+/// An error for the bytes `span` of `input`, cut off at the end of the line.
+fn error_at(input: &str, span: Range<usize>, expected: &str) -> ParseError {
+    let line_idx = input[..span.start].matches('\n').count();
+    let line_start = input[..span.start].rfind('\n').map_or(0, |pos| pos + 1);
+    let line = input[line_start..].lines().next().unwrap_or_default();
+    let end = span.end.clamp(span.start, line_start + line.len());
+
+    ParseError::at(
+        line_idx,
+        line,
+        &line[span.start - line_start..end - line_start],
+        expected,
+    )
+}
+
+/// The ids of the games possible with each of the bags, in the order of the bags.
+///
+/// The smallest bag of every game is computed only once for all bags.
+pub fn possible_games(games: &[Game], bags: &[Bag]) -> Vec<Vec<u64>> {
+    let required: Vec<_> = games
+        .iter()
+        .map(|game| (game.id(), game.min_required()))
+        .collect();
+
+    bags.iter()
+        .map(|bag| {
+            required
+                .iter()
+                .filter(|(_, round)| bag.contains(round))
+                .map(|(id, _)| *id)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    use crate::{parse_input, EXAMPLE};
+
+    #[test_case("red=12,green=13,blue=14" => Ok(Bag::default()))]
+    #[test_case(" red = 1 , yellow=2" => Ok(Bag::new().with("red", 1).with("yellow", 2)))]
+    #[test_case("red=12,green" => Err(ParseError::new(1, 8, "green", "`<colour>=<count>`")))]
+    #[test_case("red=12,=3" => Err(ParseError::new(1, 8, "", "a colour name")))]
+    #[test_case("red=-1" => Err(ParseError::new(1, 5, "-1", "a cube count")))]
+    fn test_from_flags(flags: &str) -> Result<Bag, ParseError> {
+        Bag::from_flags(flags)
+    }
+
+    #[test_case("red = 12\ngreen = 13\nblue = 14\n" => Ok(Bag::default()))]
+    #[test_case("# limits\ncyan = 3" => Ok(Bag::new().with("cyan", 3)))]
+    #[test_case("red = 12\ngreen = \"many\"" => Err(ParseError::new(2, 9, "\"many\"", "a table of colours to cube counts")))]
+    #[test_case("red = 12\npur-ple = 3" => Err(ParseError::new(2, 1, "pur-ple", "a colour name")))]
+    #[test_case("red = 12\n\"light blue\" = 3" => Err(ParseError::new(2, 2, "light blue", "a colour name")))]
+    fn test_from_toml(input: &str) -> Result<Bag, ParseError> {
+        Bag::from_toml(input)
+    }
+
+    #[test_case(r#"{"red": 12, "green": 13, "blue": 14}"# => Ok(Bag::default()))]
+    #[test_case("{\n  \"red\": -1\n}" => Err(ParseError::new(2, 10, "-1", "an object of colours to cube counts")))]
+    #[test_case(r#"{"red": 12, "blue": many}"# => Err(ParseError::new(1, 21, "many", "an object of colours to cube counts")))]
+    #[test_case(r#"{"red": 12, "pur-ple": 3}"# => Err(ParseError::new(1, 14, "pur-ple", "a colour name")))]
+    #[test_case(r#"{"": 3}"# => Err(ParseError::new(1, 3, "", "a colour name")))]
+    fn test_from_json(input: &str) -> Result<Bag, ParseError> {
+        Bag::from_json(input)
+    }
+
+    #[test_case(Bag::default() => vec![1, 2, 5])]
+    #[test_case(Bag::new() => Vec::<u64>::new())]
+    #[test_case(Bag::new().with("red", 20).with("green", 13).with("blue", 15) => vec![1, 2, 3, 4, 5])]
+    #[test_case(Bag::new().with("red", 6).with("green", 3).with("blue", 4) => vec![2, 5])]
+    fn test_game_possible(bag: Bag) -> Vec<u64> {
+        let games = parse_input(EXAMPLE).unwrap();
+
+        games
+            .iter()
+            .filter(|game| game.is_possible(&bag))
+            .map(Game::id)
+            .collect()
+    }
+
+    #[test]
+    fn test_possible_games() {
+        let games = parse_input(EXAMPLE).unwrap();
+        let bags = [
+            Bag::default(),
+            Bag::new()
+                .with("red", 20)
+                .with("green", 13)
+                .with("blue", 15),
+            Bag::new(),
+        ];
+
+        assert_eq!(
+            possible_games(&games, &bags),
+            [vec![1, 2, 5], vec![1, 2, 3, 4, 5], vec![]]
+        );
+    }
+}
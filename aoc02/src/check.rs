@@ -0,0 +1,146 @@
+use std::fmt::{self, Display};
+
+use serde_json::{json, Value};
+
+use crate::{Bag, Game};
+
+/// A colour drawn more often in a round than the bag holds.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Violation {
+    /// 1-based number of the round within the game, as in the puzzle
+    pub round: usize,
+    pub colour: String,
+    pub drawn: u64,
+    pub limit: u64,
+}
+
+/// Why a game is possible with a bag or not.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Check {
+    pub id: u64,
+    pub violations: Vec<Violation>,
+}
+
+impl Game {
+    /// Lists every round and colour exceeding `bag`.
+    pub fn check(&self, bag: &Bag) -> Check {
+        let violations = self
+            .rounds()
+            .iter()
+            .zip(1..)
+            .flat_map(|(round, number)| {
+                round
+                    .iter()
+                    .filter(|(colour, drawn)| *drawn > bag.get(colour))
+                    .map(move |(colour, drawn)| Violation {
+                        round: number,
+                        colour: colour.to_owned(),
+                        drawn,
+                        limit: bag.get(colour),
+                    })
+            })
+            .collect();
+
+        Check {
+            id: self.id(),
+            violations,
+        }
+    }
+}
+
+impl Check {
+    pub fn is_possible(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn to_json(&self) -> Value {
+        let violations: Vec<_> = self
+            .violations
+            .iter()
+            .map(|violation| {
+                json!({
+                    "round": violation.round,
+                    "colour": violation.colour,
+                    "drawn": violation.drawn,
+                    "limit": violation.limit,
+                })
+            })
+            .collect();
+
+        json!({ "game": self.id, "possible": self.is_possible(), "violations": violations })
+    }
+}
+
+impl Display for Check {
+    /// Names the game and the violations below it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_possible() {
+            return write!(f, "Game {} is possible", self.id);
+        }
+
+        write!(f, "Game {} is impossible:", self.id)?;
+        for violation in &self.violations {
+            write!(
+                f,
+                "\n  round {}: drew {} {}, the bag holds {}",
+                violation.round, violation.drawn, violation.colour, violation.limit
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    use crate::parse_input;
+
+    #[test_case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green" => Vec::<Violation>::new())]
+    #[test_case(
+        "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"
+        => vec![Violation { round: 1, colour: "red".to_owned(), drawn: 20, limit: 12 }]
+    )]
+    #[test_case(
+        "Game 7: 15 blue, 14 red; 1 yellow"
+        => vec![
+            Violation { round: 1, colour: "blue".to_owned(), drawn: 15, limit: 14 },
+            Violation { round: 1, colour: "red".to_owned(), drawn: 14, limit: 12 },
+            Violation { round: 2, colour: "yellow".to_owned(), drawn: 1, limit: 0 },
+        ]
+    )]
+    fn test_check(line: &str) -> Vec<Violation> {
+        let game = parse_input(line).unwrap().pop().unwrap();
+
+        game.check(&Bag::default()).violations
+    }
+
+    #[test_case("Game 1: 3 blue, 4 red" => "Game 1 is possible")]
+    #[test_case(
+        "Game 37: 2 red; 15 blue, 14 red"
+        => "Game 37 is impossible:
+  round 2: drew 15 blue, the bag holds 14
+  round 2: drew 14 red, the bag holds 12"
+    )]
+    fn test_display(line: &str) -> String {
+        let game = parse_input(line).unwrap().pop().unwrap();
+
+        game.check(&Bag::default()).to_string()
+    }
+
+    #[test]
+    fn test_to_json() {
+        let game = parse_input("Game 4: 1 green; 3 red, 15 blue")
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        assert_eq!(
+            game.check(&Bag::default()).to_json().to_string(),
+            r#"{"game":4,"possible":false,"violations":[{"colour":"blue","drawn":15,"limit":14,"round":2}]}"#
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Estimates the bag a game was played with from the rounds drawn.
+//!
+//! Within a round the cubes are drawn without replacement, so the counts follow a
+//! multivariate hypergeometric distribution. The cubes go back into the bag between
+//! rounds, making the rounds independent of each other.
+
+use crate::{Bag, Round};
+
+/// Products of fewer factors than this are summed up term by term, larger factorials
+/// use Stirling's series.
+const EXACT_FACTORIALS: u128 = 256;
+
+/// The natural logarithm of `n!`.
+fn ln_factorial(n: u128) -> f64 {
+    if n < EXACT_FACTORIALS {
+        return (2..=n).map(|i| (i as f64).ln()).sum();
+    }
+
+    let n = n as f64;
+    n * n.ln() - n + 0.5 * (std::f64::consts::TAU * n).ln() + 1.0 / (12.0 * n)
+        - 1.0 / (360.0 * n.powi(3))
+}
+
+/// The natural logarithm of `n` choose `k`, negative infinity if `k > n`.
+fn ln_choose(n: u128, k: u128) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+
+    // few factors avoid the cancellation between the huge factorials of big bags
+    let k = k.min(n - k);
+    if k < EXACT_FACTORIALS {
+        return (0..k).map(|i| ((n - i) as f64).ln()).sum::<f64>() - ln_factorial(k);
+    }
+
+    ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k)
+}
+
+/// The natural logarithm of the probability to draw `rounds` from `bag`,
+/// negative infinity if they can not be drawn from it.
+pub fn log_likelihood(rounds: &[Round], bag: &Bag) -> f64 {
+    // summed up wider than the counts, so huge bags can not overflow
+    let total = bag.iter().map(|(_, count)| count as u128).sum();
+
+    rounds
+        .iter()
+        .map(|round| {
+            // both binomials would be negative infinity, leaving NaN behind
+            if !bag.contains(round) {
+                return f64::NEG_INFINITY;
+            }
+
+            let drawn = round.iter().map(|(_, count)| count as u128).sum();
+            let ways: f64 = round
+                .iter()
+                .map(|(colour, count)| ln_choose(bag.get(colour) as u128, count as u128))
+                .sum();
+
+            ways - ln_choose(total, drawn)
+        })
+        .sum()
+}
+
+/// The probability to draw `rounds` from `bag`.
+pub fn likelihood(rounds: &[Round], bag: &Bag) -> f64 {
+    log_likelihood(rounds, bag).exp()
+}
+
+/// A distribution over bags holding the drawn colours.
+#[derive(Clone, Debug)]
+pub struct Posterior {
+    /// Every bag of the grid that could produce the rounds, with its probability
+    pub bags: Vec<(Bag, f64)>,
+}
+
+impl Posterior {
+    /// The most probable bag, the one with fewer cubes on ties.
+    pub fn mode(&self) -> Option<&Bag> {
+        self.bags
+            .iter()
+            .max_by(|(a, p), (b, q)| p.total_cmp(q).then(b.total().cmp(&a.total())))
+            .map(|(bag, _)| bag)
+    }
+
+    /// The probability of every number of cubes of `colour`, by ascending count.
+    pub fn marginal(&self, colour: &str) -> Vec<(u64, f64)> {
+        let mut marginal: Vec<(u64, f64)> = vec![];
+        for (bag, probability) in &self.bags {
+            let count = bag.get(colour);
+            match marginal.iter_mut().find(|(known, _)| *known == count) {
+                Some((_, sum)) => *sum += probability,
+                None => marginal.push((count, *probability)),
+            }
+        }
+        marginal.sort_by_key(|(count, _)| *count);
+
+        marginal
+    }
+
+    /// The expected number of cubes of `colour`.
+    pub fn mean(&self, colour: &str) -> f64 {
+        self.bags
+            .iter()
+            .map(|(bag, probability)| bag.get(colour) as f64 * probability)
+            .sum()
+    }
+}
+
+/// The posterior over all bags with at most `max` cubes of each drawn colour, starting
+/// from a uniform prior. Empty if no such bag can produce the rounds.
+pub fn posterior(rounds: &[Round], max: u64) -> Posterior {
+    let required = rounds
+        .iter()
+        .fold(Round::default(), |min, round| min.partial_max(round));
+    let colours: Vec<_> = required.iter().collect();
+    if colours.iter().any(|(_, min)| *min > max) {
+        return Posterior { bags: vec![] };
+    }
+
+    let mut bags = vec![];
+    let mut counts: Vec<_> = colours.iter().map(|(_, min)| *min).collect();
+    loop {
+        let bag = colours
+            .iter()
+            .zip(&counts)
+            .fold(Bag::new(), |bag, ((colour, _), count)| {
+                bag.with(*colour, *count)
+            });
+        let log_likelihood = log_likelihood(rounds, &bag);
+        bags.push((bag, log_likelihood));
+
+        // advance the counts like an odometer, the last colour fastest
+        let Some(idx) = counts.iter().rposition(|count| *count < max) else {
+            break;
+        };
+        counts[idx] += 1;
+        for (count, (_, min)) in counts.iter_mut().zip(&colours).skip(idx + 1) {
+            *count = *min;
+        }
+    }
+
+    // normalise in log space, the likelihoods themselves underflow quickly
+    let top = bags
+        .iter()
+        .map(|(_, log)| *log)
+        .fold(f64::NEG_INFINITY, f64::max);
+    for (_, log) in bags.iter_mut() {
+        *log = (*log - top).exp();
+    }
+    let sum: f64 = bags.iter().map(|(_, weight)| weight).sum();
+    for (_, weight) in bags.iter_mut() {
+        *weight /= sum;
+    }
+
+    Posterior { bags }
+}
+
+/// The bag of at most `max` cubes per colour most likely to produce the rounds.
+pub fn maximum_likelihood(rounds: &[Round], max: u64) -> Option<Bag> {
+    posterior(rounds, max).mode().cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+    use test_case::test_case;
+
+    use crate::parse_input;
+
+    fn rounds(line: &str) -> Vec<Round> {
+        parse_input(line).unwrap().pop().unwrap().rounds().to_vec()
+    }
+
+    /// Draws `size` cubes per round from `bag`, putting them back in between.
+    fn simulate(rng: &mut StdRng, bag: &Bag, rounds: usize, size: usize) -> Vec<Round> {
+        let mut cubes: Vec<_> = bag
+            .iter()
+            .flat_map(|(colour, count)| (0..count).map(move |_| colour))
+            .collect();
+
+        (0..rounds)
+            .map(|_| {
+                cubes.shuffle(rng);
+                cubes[..size]
+                    .iter()
+                    .fold(Round::default(), |mut round, colour| {
+                        round.set(*colour, round.get(colour) + 1);
+                        round
+                    })
+            })
+            .collect()
+    }
+
+    #[test_case("Game 1: 3 red, 2 blue", Bag::new().with("red", 3).with("blue", 2), 1.0)]
+    #[test_case("Game 1: 1 red, 1 blue", Bag::new().with("red", 2).with("blue", 2), 4.0 / 6.0)]
+    #[test_case("Game 1: 1 red; 1 red", Bag::new().with("red", 1).with("blue", 1), 0.25)]
+    #[test_case("Game 1: 2 red", Bag::new().with("red", 1).with("blue", 1), 0.0)]
+    #[test_case("Game 1: 1 green", Bag::default(), 13.0 / 39.0)]
+    #[test_case("Game 1: 1 cyan", Bag::default(), 0.0)]
+    #[test_case("Game 1: 2 red", Bag::new().with("red", 1), 0.0)]
+    #[test_case("Game 1: 2 red", Bag::new(), 0.0)]
+    fn test_likelihood(line: &str, bag: Bag, expected: f64) {
+        let probability = likelihood(&rounds(line), &bag);
+        assert!((probability - expected).abs() < 1e-9, "{}", probability);
+    }
+
+    #[test_case(0)]
+    #[test_case(1)]
+    #[test_case(EXACT_FACTORIALS - 1)]
+    #[test_case(EXACT_FACTORIALS)]
+    #[test_case(10_000)]
+    fn test_ln_factorial(n: u128) {
+        let exact: f64 = (2..=n).map(|i| (i as f64).ln()).sum();
+
+        assert!((ln_factorial(n) - exact).abs() < 1e-9 * exact.max(1.0));
+    }
+
+    #[test_case("Game 1: 1 red", u64::MAX, u64::MAX, 0.5)]
+    #[test_case("Game 1: 1 red, 1 blue", 1 << 40, 1 << 40, 0.5)]
+    #[test_case("Game 1: 18446744073709551615 red", u64::MAX, 0, 1.0)]
+    fn test_likelihood_huge(line: &str, red: u64, blue: u64, expected: f64) {
+        let bag = Bag::new().with("red", red).with("blue", blue);
+
+        let probability = likelihood(&rounds(line), &bag);
+        assert!((probability - expected).abs() < 1e-6, "{}", probability);
+    }
+
+    #[test_case("Game 1: 2 red", Bag::new().with("red", 1))]
+    #[test_case("Game 1: 1 red; 2 red", Bag::new())]
+    fn test_log_likelihood_impossible(line: &str, bag: Bag) {
+        assert_eq!(log_likelihood(&rounds(line), &bag), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_posterior() {
+        let posterior = posterior(&rounds("Game 1: 2 red, 1 blue; 1 red, 2 blue"), 6);
+
+        // red and blue each range over 2..=6
+        assert_eq!(posterior.bags.len(), 25);
+        let sum: f64 = posterior.bags.iter().map(|(_, p)| p).sum();
+        assert!((sum - 1.0).abs() < 1e-9);
+        assert_eq!(
+            posterior
+                .marginal("red")
+                .iter()
+                .map(|(count, _)| *count)
+                .collect::<Vec<_>>(),
+            [2, 3, 4, 5, 6]
+        );
+        assert!((posterior.mean("red") - posterior.mean("blue")).abs() < 1e-9);
+    }
+
+    #[test_case("Game 1: 3 red, 2 blue" => Some(Bag::new().with("red", 3).with("blue", 2)))]
+    #[test_case("Game 1: 1 red, 1 blue; 2 red" => Some(Bag::new().with("red", 3).with("blue", 1)))]
+    #[test_case("Game 1: 7 red" => None)]
+    fn test_maximum_likelihood(line: &str) -> Option<Bag> {
+        maximum_likelihood(&rounds(line), 6)
+    }
+
+    #[test]
+    fn test_recovers_simulated() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let bag = Bag::new().with("red", 6).with("green", 2).with("blue", 4);
+
+        let rounds = simulate(&mut rng, &bag, 300, 5);
+        let posterior = posterior(&rounds, 12);
+
+        let estimate = posterior.mode().unwrap();
+        for (colour, count) in bag.iter() {
+            assert!(estimate.get(colour).abs_diff(count) <= 1, "{:?}", estimate);
+            assert!(
+                (posterior.mean(colour) - count as f64).abs() < 1.5,
+                "{}",
+                colour
+            );
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use std::collections::{BTreeMap, HashSet};
+
+use aoc_core::{ParseError, Solution};
+
+mod bag;
+mod check;
+mod inference;
+mod search;
+
+pub use bag::{possible_games, Bag};
+pub use check::{Check, Violation};
+pub use inference::{likelihood, log_likelihood, maximum_likelihood, posterior, Posterior};
+pub use search::{best_bag_within, pareto_frontier, smallest_bag, smallest_bag_by};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer, ParseError> {
+        Ok(sum_possible_ids(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer, ParseError> {
+        sum_of_power(input)
+    }
+}
+
+/// The cubes drawn in a single round, by colour.
+///
+/// Colours that were not drawn are left out, so they count as zero.
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct Round {
+    counts: BTreeMap<String, u64>,
+}
+
+impl Round {
+    /// Number of cubes drawn in `colour`.
+    pub fn get(&self, colour: &str) -> u64 {
+        self.counts.get(colour).copied().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, colour: impl Into<String>, count: u64) {
+        let colour = colour.into();
+        if count == 0 {
+            self.counts.remove(&colour);
+        } else {
+            self.counts.insert(colour, count);
+        }
+    }
+
+    /// The colours drawn in alphabetical order, with their counts.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.counts
+            .iter()
+            .map(|(colour, count)| (colour.as_str(), *count))
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.counts.keys().map(String::as_str)
+    }
+
+    pub fn partial_max(&self, other: &Self) -> Self {
+        let mut max = self.clone();
+        for (colour, count) in other.iter() {
+            if count > max.get(colour) {
+                max.set(colour, count);
+            }
+        }
+
+        max
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, u64)> for Round {
+    fn from_iter<T: IntoIterator<Item = (S, u64)>>(iter: T) -> Self {
+        let mut round = Self::default();
+        for (colour, count) in iter {
+            round.set(colour, count);
+        }
+
+        round
+    }
+}
+
+pub struct Game {
+    id: u64,
+    rounds: Vec<Round>,
+}
+
+impl Game {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    /// Whether every round can be drawn from `bag`.
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        self.rounds.iter().all(|round| bag.contains(round))
+    }
+
+    /// Whether the game can not be played with the [default bag](Bag::default).
+    pub fn is_impossible(&self) -> bool {
+        !self.is_possible(&Bag::default())
+    }
+
+    pub fn min_required(&self) -> Round {
+        let mut min = Round::default();
+        for round in &self.rounds {
+            min = min.partial_max(round);
+        }
+
+        min
+    }
+
+    /// The product of the minimal cube counts over `colours`, zero if the game
+    /// never draws one of them.
+    ///
+    /// `None` if the power does not fit into a `u64`.
+    pub fn get_power(&self, colours: &[&str]) -> Option<u64> {
+        let min = self.min_required();
+        colours
+            .iter()
+            .try_fold(1u64, |power, colour| power.checked_mul(min.get(colour)))
+    }
+}
+
+/// Whether `name` can name a colour, which takes one or more letters.
+pub(crate) fn is_colour(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(char::is_alphabetic)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Game>, ParseError> {
+    let mut res = vec![];
+    for (line_idx, raw_line) in input.lines().enumerate() {
+        let err =
+            |snippet: &str, expected: &str| ParseError::at(line_idx, raw_line, snippet, expected);
+
+        let line = raw_line.trim();
+        let (game_part, color_part) = line
+            .split_once(':')
+            .ok_or_else(|| err(&line[line.len()..], "`:`"))?;
+        let (_, idx) = game_part
+            .split_once(' ')
+            .ok_or_else(|| err(game_part, "`Game <id>`"))?;
+        let id: u64 = idx.parse().map_err(|_| err(idx, "a game id"))?;
+
+        let mut game = Game { id, rounds: vec![] };
+
+        let rounds = color_part
+            .trim()
+            .split(';')
+            .map(|color| color.trim().split(", "));
+
+        for round in rounds {
+            let mut rnd = Round::default();
+            // zero counts are not kept in the round, so remember every colour named
+            let mut seen = HashSet::new();
+
+            for color in round {
+                let color = color.trim();
+                let (amount, cname) = color
+                    .split_once(' ')
+                    .ok_or_else(|| err(color, "`<count> <color>`"))?;
+                let amount: u64 = amount
+                    .trim()
+                    .parse()
+                    .map_err(|_| err(amount, "a cube count"))?;
+                if !is_colour(cname) {
+                    return Err(err(cname, "a colour name"));
+                }
+                if !seen.insert(cname) {
+                    return Err(err(cname, "a colour not drawn before in the round"));
+                }
+                rnd.set(cname, amount);
+            }
+
+            game.rounds.push(rnd);
+        }
+
+        res.push(game);
+    }
+
+    Ok(res)
+}
+
+pub fn sum_possible_ids(games: &[Game]) -> u64 {
+    sum_possible_ids_with(games, &Bag::default())
+}
+
+pub fn sum_possible_ids_with(games: &[Game], bag: &Bag) -> u64 {
+    games
+        .iter()
+        .filter(|game| game.is_possible(bag))
+        .map(|game| game.id)
+        .sum()
+}
+
+/// Every colour drawn in any of the games, in alphabetical order.
+pub fn colours(games: &[Game]) -> Vec<&str> {
+    let mut colours: Vec<_> = games
+        .iter()
+        .flat_map(|game| &game.rounds)
+        .flat_map(Round::colours)
+        .collect();
+    colours.sort_unstable();
+    colours.dedup();
+
+    colours
+}
+
+/// Sums the powers over the colours of all games, so a game missing one is worth zero.
+///
+/// Fails at the first game whose power pushes the sum beyond a `u64`.
+pub fn sum_of_power(games: &[Game]) -> Result<u64, ParseError> {
+    let colours = colours(games);
+
+    games.iter().enumerate().try_fold(0u64, |sum, (idx, game)| {
+        game.get_power(&colours)
+            .and_then(|power| sum.checked_add(power))
+            .ok_or_else(|| {
+                ParseError::new(
+                    idx + 1,
+                    1,
+                    format!("Game {}", game.id),
+                    "a sum of powers fitting into 64 bits",
+                )
+            })
+    })
+}
+
+/// The games from the puzzle description, shared by the tests of all modules.
+#[cfg(test)]
+const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+        Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+        Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+        Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")]
+    #[test_case("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue")]
+    #[test_case("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red")]
+    #[test_case("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red")]
+    #[test_case("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green")]
+    fn test_parser(line: &str) -> Result<(), ()> {
+        let games = parse_input(line).unwrap();
+
+        assert!(games.len() == 1);
+        Ok(())
+    }
+
+    #[test_case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", 4, 2, 6)]
+    #[test_case(
+        "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+        1,
+        3,
+        4
+    )]
+    #[test_case(
+        "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        20,
+        13,
+        6
+    )]
+    #[test_case(
+        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+        14,
+        3,
+        15
+    )]
+    #[test_case("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", 6, 3, 2)]
+    fn test_min_req(line: &str, red: u64, green: u64, blue: u64) {
+        let game = parse_input(line).unwrap().pop().unwrap();
+        let min_req = game.min_required();
+
+        let desired = Round::from_iter([("red", red), ("green", green), ("blue", blue)]);
+        assert_eq!(min_req, desired);
+    }
+
+    #[test_case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", 48)]
+    #[test_case("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue", 12)]
+    #[test_case(
+        "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        1560
+    )]
+    #[test_case(
+        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+        630
+    )]
+    #[test_case("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", 36)]
+    fn test_power(line: &str, power: u64) {
+        let game = parse_input(line).unwrap().pop().unwrap();
+
+        assert_eq!(game.get_power(&["blue", "green", "red"]), Some(power));
+    }
+
+    #[test_case("Game 1: 3 yellow, 4 red; 1 red, 2 cyan", &[("cyan", 2), ("red", 4), ("yellow", 3)])]
+    #[test_case("Game 1: 0 red; 2 blue", &[("blue", 2)])]
+    fn test_colours(line: &str, desired: &[(&str, u64)]) {
+        let game = parse_input(line).unwrap().pop().unwrap();
+        let min_req = game.min_required();
+
+        assert_eq!(min_req.iter().collect::<Vec<_>>(), desired);
+        assert_eq!(min_req, Round::from_iter(desired.iter().copied()));
+    }
+
+    #[test_case("Game 1: 3 yellow, 4 red; 1 red, 2 cyan" => Ok(24))]
+    #[test_case("Game 1: 1 blue; 2 blue" => Ok(2))]
+    #[test_case("Game 1: 1 blue; 2 blue\nGame 2: 1 red, 2 green, 3 blue" => Ok(6))]
+    #[test_case("Game 1: 3 yellow\nGame 2: 2 red; 1 red" => Ok(0))]
+    #[test_case(
+        "Game 1: 1 red\nGame 2: 100 a, 100 b, 100 c, 100 d, 100 e, 100 f, 100 g, 100 h, 100 i, 100 j, 100 k"
+        => Err(ParseError::new(2, 1, "Game 2", "a sum of powers fitting into 64 bits"))
+    )]
+    #[test_case(
+        "Game 1: 18446744073709551615 red\nGame 2: 1 red"
+        => Err(ParseError::new(2, 1, "Game 2", "a sum of powers fitting into 64 bits"))
+    )]
+    fn test_power_colours(input: &str) -> Result<u64, ParseError> {
+        sum_of_power(&parse_input(input).unwrap())
+    }
+
+    #[test_case("Game 1: 12 red, 13 green, 14 blue" => false)]
+    #[test_case("Game 1: 13 red" => true)]
+    #[test_case("Game 1: 1 yellow" => true)]
+    fn test_impossible(line: &str) -> bool {
+        parse_input(line).unwrap().pop().unwrap().is_impossible()
+    }
+
+    #[test]
+    fn test_solution() {
+        let games = Day02::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day02::part1(&games), Ok(8));
+        assert_eq!(Day02::part2(&games), Ok(2286));
+    }
+
+    #[test_case("Game 1 3 blue, 4 red" => ParseError::new(1, 21, "", "`:`"))]
+    #[test_case("Game1: 3 blue, 4 red" => ParseError::new(1, 1, "Game1", "`Game <id>`"))]
+    #[test_case("Game x: 3 blue, 4 red" => ParseError::new(1, 6, "x", "a game id"))]
+    #[test_case("Game 1: 3 blue, 4red" => ParseError::new(1, 17, "4red", "`<count> <color>`"))]
+    #[test_case("Game 1: 3 blue; many red" => ParseError::new(1, 17, "many", "a cube count"))]
+    #[test_case(
+        "Game 1: 3 blue\nGame 2: 1 red, 2 pur-ple"
+        => ParseError::new(2, 18, "pur-ple", "a colour name")
+    )]
+    #[test_case(
+        "Game 1: 3 blue, 2 blue"
+        => ParseError::new(1, 19, "blue", "a colour not drawn before in the round")
+    )]
+    #[test_case(
+        "Game 1: 0 red, 1 red"
+        => ParseError::new(1, 18, "red", "a colour not drawn before in the round")
+    )]
+    #[test_case(
+        "Game 1: 18446744073709551615 red, 1 red"
+        => ParseError::new(1, 37, "red", "a colour not drawn before in the round")
+    )]
+    fn test_parse_error(input: &str) -> ParseError {
+        parse_input(input).err().unwrap()
+    }
+}
@@ -0,0 +1,333 @@
+//! Queries over a whole set of games, asking which bags make many of them possible.
+//!
+//! A bag makes a game possible exactly if it holds the game's [`Game::min_required`] cubes,
+//! so the best limit of every colour is one of the games' requirements. The searches
+//! enumerate those limits for all colours but the last one and pick the last limit
+//! directly, which is fast for the few colours of the puzzle.
+
+use crate::{Bag, Game};
+
+/// The minimal requirements of the games, as counts per colour.
+struct Requirements {
+    colours: Vec<String>,
+    /// Game id and required count per colour
+    games: Vec<(u64, Vec<u64>)>,
+    /// Distinct requirements per colour, ascending and starting at zero
+    values: Vec<Vec<u64>>,
+}
+
+impl Requirements {
+    fn new(games: &[Game]) -> Self {
+        let required: Vec<_> = games
+            .iter()
+            .map(|game| (game.id(), game.min_required()))
+            .collect();
+
+        let mut colours: Vec<String> = required
+            .iter()
+            .flat_map(|(_, round)| round.colours().map(str::to_owned))
+            .collect();
+        colours.sort_unstable();
+        colours.dedup();
+
+        let games: Vec<(u64, Vec<u64>)> = required
+            .iter()
+            .map(|(id, round)| (*id, colours.iter().map(|c| round.get(c)).collect()))
+            .collect();
+        let values = (0..colours.len())
+            .map(|idx| {
+                let mut values: Vec<_> = games.iter().map(|(_, req)| req[idx]).collect();
+                values.push(0);
+                values.sort_unstable();
+                values.dedup();
+                values
+            })
+            .collect();
+
+        Self {
+            colours,
+            games,
+            values,
+        }
+    }
+
+    fn bag(&self, prefix: &[u64], last: u64) -> Bag {
+        prefix
+            .iter()
+            .chain([&last])
+            .zip(&self.colours)
+            .filter(|(limit, _)| **limit > 0)
+            .fold(Bag::new(), |bag, (limit, colour)| bag.with(colour, *limit))
+    }
+
+    /// Calls `visit` with every combination of limits for all colours but the last one,
+    /// and the games fitting them as id and requirement of the last colour.
+    fn enumerate(&self, mut visit: impl FnMut(&[u64], &[(u64, u64)])) {
+        let all: Vec<_> = self.games.iter().collect();
+
+        if self.colours.is_empty() {
+            let fitting: Vec<_> = all.iter().map(|(id, _)| (*id, 0)).collect();
+            visit(&[], &fitting);
+        } else {
+            self.recurse(&mut vec![], &all, &mut visit);
+        }
+    }
+
+    fn recurse(
+        &self,
+        prefix: &mut Vec<u64>,
+        fitting: &[&(u64, Vec<u64>)],
+        visit: &mut impl FnMut(&[u64], &[(u64, u64)]),
+    ) {
+        let idx = prefix.len();
+        if idx + 1 == self.colours.len() {
+            let last: Vec<_> = fitting.iter().map(|(id, req)| (*id, req[idx])).collect();
+            visit(prefix, &last);
+            return;
+        }
+
+        for limit in &self.values[idx] {
+            let next: Vec<_> = fitting
+                .iter()
+                .filter(|(_, req)| req[idx] <= *limit)
+                .copied()
+                .collect();
+
+            prefix.push(*limit);
+            self.recurse(prefix, &next, visit);
+            prefix.pop();
+        }
+    }
+}
+
+/// The bag with the fewest cubes that makes at least `k` games possible.
+pub fn smallest_bag(games: &[Game], k: usize) -> Option<Bag> {
+    smallest_bag_by(games, k, Bag::total)
+}
+
+/// The bag minimising `metric` that makes at least `k` games possible, `None` if there
+/// are fewer games.
+///
+/// The metric must not decrease when a limit grows, like the total or a weighted sum.
+pub fn smallest_bag_by(games: &[Game], k: usize, metric: impl Fn(&Bag) -> u64) -> Option<Bag> {
+    if k == 0 {
+        return Some(Bag::new());
+    }
+
+    let req = Requirements::new(games);
+    let mut best: Option<(u64, Bag)> = None;
+    req.enumerate(|prefix, fitting| {
+        if fitting.len() < k {
+            return;
+        }
+
+        let mut last: Vec<_> = fitting.iter().map(|(_, last)| *last).collect();
+        last.sort_unstable();
+        let bag = req.bag(prefix, last[k - 1]);
+        let cost = metric(&bag);
+        if best.as_ref().is_none_or(|(best, _)| cost < *best) {
+            best = Some((cost, bag));
+        }
+    });
+
+    best.map(|(_, bag)| bag)
+}
+
+/// The bags trading total cubes against possible games, each with the number of games
+/// it makes possible.
+///
+/// Every bag makes more games possible than the previous ones, and no bag with as many
+/// cubes or fewer does as well.
+pub fn pareto_frontier(games: &[Game]) -> Vec<(Bag, usize)> {
+    let req = Requirements::new(games);
+
+    // the cheapest bag per number of possible games
+    let mut best: Vec<Option<(u64, Bag)>> = vec![None; games.len() + 1];
+    req.enumerate(|prefix, fitting| {
+        let base: u64 = prefix.iter().sum();
+        let mut last: Vec<_> = fitting.iter().map(|(_, last)| *last).collect();
+        last.sort_unstable();
+
+        for (idx, limit) in last.iter().enumerate() {
+            let total = base + limit;
+            let slot = &mut best[idx + 1];
+            if slot.as_ref().is_none_or(|(best, _)| total < *best) {
+                *slot = Some((total, req.bag(prefix, *limit)));
+            }
+        }
+    });
+
+    let best: Vec<_> = best.into_iter().enumerate().skip(1).collect();
+    best.iter()
+        .enumerate()
+        .filter_map(|(pos, (count, entry))| {
+            let (total, bag) = entry.as_ref()?;
+            let dominated = best
+                .get(pos + 1)
+                .and_then(|(_, next)| next.as_ref())
+                .is_some_and(|(next, _)| next <= total);
+
+            (!dominated).then(|| (bag.clone(), *count))
+        })
+        .collect()
+}
+
+/// The bag of at most `budget` cubes maximising the sum of the possible game ids,
+/// along with that sum. Prefers fewer cubes among equally good bags.
+pub fn best_bag_within(games: &[Game], budget: u64) -> (Bag, u64) {
+    let req = Requirements::new(games);
+
+    let mut best = (0, 0, Bag::new());
+    req.enumerate(|prefix, fitting| {
+        let base: u64 = prefix.iter().sum();
+        if base > budget {
+            return;
+        }
+
+        let fitting = fitting.iter().filter(|(_, last)| base + last <= budget);
+        let sum: u64 = fitting.clone().map(|(id, _)| id).sum();
+        let last = fitting.map(|(_, last)| *last).max().unwrap_or_default();
+        let total = base + last;
+
+        if sum > best.0 || (sum == best.0 && total < best.1) {
+            best = (sum, total, req.bag(prefix, last));
+        }
+    });
+
+    (best.2, best.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use test_case::test_case;
+
+    use crate::{parse_input, Round, EXAMPLE};
+
+    fn random_games(rng: &mut StdRng, count: usize) -> String {
+        let colours = ["red", "green", "blue", "cyan"];
+
+        (1..=count)
+            .map(|id| {
+                // zero counts leave the colour out of the game
+                let draws: Vec<_> = colours
+                    .iter()
+                    .map(|colour| format!("{} {}", rng.gen_range(0..=6), colour))
+                    .collect();
+                format!("Game {}: {}", id, draws.join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Tries every subset of games, taking the smallest bag holding all of them.
+    fn brute_force(games: &[Game]) -> Vec<(u64, usize, u64)> {
+        (0..1u32 << games.len())
+            .map(|subset| {
+                let bag = games
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, _)| subset & (1 << idx) != 0)
+                    .fold(Round::default(), |bag, (_, game)| {
+                        bag.partial_max(&game.min_required())
+                    });
+                let total = bag.iter().map(|(_, count)| count).sum();
+                let possible = games
+                    .iter()
+                    .filter(|game| game.min_required().partial_max(&bag) == bag);
+
+                (
+                    total,
+                    possible.clone().count(),
+                    possible.map(Game::id).sum(),
+                )
+            })
+            .collect()
+    }
+
+    #[test_case(0 => Some(0))]
+    #[test_case(1 => Some(8))]
+    #[test_case(2 => Some(13))]
+    #[test_case(3 => Some(15))]
+    #[test_case(4 => Some(32))]
+    #[test_case(5 => Some(48))]
+    #[test_case(6 => None)]
+    fn test_smallest_bag(k: usize) -> Option<u64> {
+        let games = parse_input(EXAMPLE).unwrap();
+        let bag = smallest_bag(&games, k)?;
+
+        let possible = games.iter().filter(|game| game.is_possible(&bag)).count();
+        assert!(possible >= k);
+        Some(bag.total())
+    }
+
+    #[test]
+    fn test_smallest_bag_by() {
+        let games = parse_input(EXAMPLE).unwrap();
+        // red cubes are expensive
+        let metric = |bag: &Bag| bag.get("red") * 10 + bag.get("green") + bag.get("blue");
+
+        let bag = smallest_bag_by(&games, 2, metric).unwrap();
+        assert_eq!(
+            bag,
+            Bag::new().with("red", 4).with("green", 3).with("blue", 6)
+        );
+    }
+
+    #[test]
+    fn test_pareto_frontier() {
+        let games = parse_input(EXAMPLE).unwrap();
+
+        let frontier: Vec<_> = pareto_frontier(&games)
+            .into_iter()
+            .map(|(bag, count)| (bag.total(), count))
+            .collect();
+        assert_eq!(frontier, [(8, 1), (13, 2), (15, 3), (32, 4), (48, 5)]);
+    }
+
+    #[test_case(0 => (Bag::new(), 0))]
+    #[test_case(12 => (Bag::new().with("red", 6).with("green", 3).with("blue", 2), 5))]
+    #[test_case(13 => (Bag::new().with("red", 6).with("green", 3).with("blue", 4), 7))]
+    #[test_case(1000 => (Bag::new().with("red", 20).with("green", 13).with("blue", 15), 15))]
+    fn test_best_bag_within(budget: u64) -> (Bag, u64) {
+        let games = parse_input(EXAMPLE).unwrap();
+
+        best_bag_within(&games, budget)
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(2);
+
+        for _ in 0..20 {
+            let count = rng.gen_range(1..=7);
+            let games = parse_input(&random_games(&mut rng, count)).unwrap();
+            let subsets = brute_force(&games);
+
+            for k in 1..=count {
+                let desired = subsets.iter().filter(|(_, possible, _)| *possible >= k);
+                let bag = smallest_bag(&games, k).unwrap();
+                assert_eq!(Some(bag.total()), desired.map(|(total, _, _)| *total).min());
+            }
+
+            for budget in [0, 5, 10, 20] {
+                let desired = subsets
+                    .iter()
+                    .filter(|(total, _, _)| *total <= budget)
+                    .map(|(_, _, ids)| *ids)
+                    .max();
+                assert_eq!(Some(best_bag_within(&games, budget).1), desired);
+            }
+
+            for (bag, count) in pareto_frontier(&games) {
+                let possible = games.iter().filter(|game| game.is_possible(&bag)).count();
+                assert_eq!(possible, count);
+                assert!(!subsets
+                    .iter()
+                    .any(|(total, possible, _)| *total <= bag.total() && *possible > count));
+            }
+        }
+    }
+}
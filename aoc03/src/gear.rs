@@ -0,0 +1,115 @@
+use std::ops::RangeInclusive;
+
+use aoc_core::ParseError;
+
+use crate::Entry;
+
+/// Combines the part numbers touching a gear into a single value.
+#[derive(Debug, Clone, Copy)]
+pub enum Reduce {
+    Sum,
+    Product,
+    Max,
+    /// Receives the parts in reading order
+    Custom(fn(&[u64]) -> u64),
+}
+
+impl Reduce {
+    /// The combined value, `None` if it does not fit into a `u64`.
+    pub fn apply(&self, parts: &[u64]) -> Option<u64> {
+        match self {
+            Self::Sum => parts
+                .iter()
+                .try_fold(0u64, |acc, part| acc.checked_add(*part)),
+            Self::Product => parts
+                .iter()
+                .try_fold(1u64, |acc, part| acc.checked_mul(*part)),
+            Self::Max => Some(parts.iter().copied().max().unwrap_or_default()),
+            Self::Custom(reduce) => Some(reduce(parts)),
+        }
+    }
+}
+
+/// Decides which symbols count as gears and what value they have.
+///
+/// A symbol is a gear if it matches `symbol` and the number of distinct parts
+/// touching it lies within `arity`.
+#[derive(Debug, Clone)]
+pub struct GearRule {
+    pub symbol: char,
+    pub arity: RangeInclusive<usize>,
+    pub reduce: Reduce,
+}
+
+impl GearRule {
+    pub fn new(symbol: char, arity: RangeInclusive<usize>, reduce: Reduce) -> Self {
+        Self {
+            symbol,
+            arity,
+            reduce,
+        }
+    }
+
+    /// Returns the value of a matching symbol with the given parts, `None` if it is no gear.
+    ///
+    /// Fails at the symbol if the value does not fit into a `u64`.
+    pub fn evaluate(&self, symbol: &Entry<char>, parts: &[u64]) -> Result<Option<u64>, ParseError> {
+        if !self.arity.contains(&parts.len()) {
+            return Ok(None);
+        }
+
+        self.reduce.apply(parts).map(Some).ok_or_else(|| {
+            ParseError::new(
+                symbol.y as usize + 1,
+                symbol.column,
+                symbol.val.to_string(),
+                "a gear value fitting into 64 bits",
+            )
+        })
+    }
+}
+
+impl Default for GearRule {
+    /// A `*` touching exactly two parts, valued by their product.
+    fn default() -> Self {
+        Self::new('*', 2..=2, Reduce::Product)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case(Reduce::Sum, &[2, 3, 4] => Some(9))]
+    #[test_case(Reduce::Sum, &[u64::MAX, 1] => None)]
+    #[test_case(Reduce::Product, &[2, 3, 4] => Some(24))]
+    #[test_case(Reduce::Product, &[1 << 32, 1 << 32] => None)]
+    #[test_case(Reduce::Max, &[2, 4, 3] => Some(4))]
+    #[test_case(Reduce::Max, &[] => Some(0))]
+    #[test_case(Reduce::Custom(|parts| parts[0]), &[2, 3, 4] => Some(2))]
+    fn test_reduce(reduce: Reduce, parts: &[u64]) -> Option<u64> {
+        reduce.apply(parts)
+    }
+
+    #[test_case(GearRule::default(), &[2] => Ok(None))]
+    #[test_case(GearRule::default(), &[2, 3] => Ok(Some(6)))]
+    #[test_case(GearRule::default(), &[2, 3, 4] => Ok(None))]
+    #[test_case(GearRule::new('#', 3..=usize::MAX, Reduce::Sum), &[2, 3, 4, 5] => Ok(Some(14)))]
+    #[test_case(
+        GearRule::default(), &[1 << 40, 1 << 40]
+        => Err(ParseError::new(3, 5, "*", "a gear value fitting into 64 bits"))
+    )]
+    fn test_evaluate(rule: GearRule, parts: &[u64]) -> Result<Option<u64>, ParseError> {
+        let symbol = Entry {
+            val: rule.symbol,
+            x: 4,
+            y: 2,
+            len: 1,
+            column: 5,
+        };
+
+        rule.evaluate(&symbol, parts)
+    }
+}
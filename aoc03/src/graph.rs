@@ -0,0 +1,208 @@
+use std::fmt::Write;
+
+use burn::tensor::backend::Backend;
+use serde_json::{json, Value};
+
+use crate::{Entry, Schematic};
+
+/// Bipartite graph between the symbols and the numbers of a schematic.
+///
+/// Symbols and numbers are referred to by their index in reading order.
+#[derive(Debug)]
+pub struct PartGraph<'a> {
+    numbers: &'a [Entry<u64>],
+    symbols: &'a [Entry<char>],
+    symbol_parts: Vec<Vec<usize>>,
+    part_symbols: Vec<Vec<usize>>,
+}
+
+impl<B: Backend> Schematic<B> {
+    pub fn graph(&self) -> PartGraph<'_> {
+        let grid = self.symbol_grid();
+
+        let mut symbol_parts = vec![vec![]; self.symbols.len()];
+        let mut part_symbols = vec![vec![]; self.numbers.len()];
+        for (num, entry) in self.numbers.iter().enumerate() {
+            for sym in self.adjacent_symbols(&grid, entry) {
+                symbol_parts[sym].push(num);
+                part_symbols[num].push(sym);
+            }
+        }
+
+        for syms in part_symbols.iter_mut() {
+            syms.sort_unstable();
+        }
+
+        PartGraph {
+            numbers: &self.numbers,
+            symbols: &self.symbols,
+            symbol_parts,
+            part_symbols,
+        }
+    }
+}
+
+impl<'a> PartGraph<'a> {
+    pub fn number(&self, num: usize) -> &'a Entry<u64> {
+        &self.numbers[num]
+    }
+
+    pub fn symbol(&self, sym: usize) -> &'a Entry<char> {
+        &self.symbols[sym]
+    }
+
+    /// The numbers touching the symbol `sym`.
+    pub fn symbol_parts(&self, sym: usize) -> &[usize] {
+        &self.symbol_parts[sym]
+    }
+
+    /// The symbols touching the number `num`, empty if it is no part.
+    pub fn part_symbols(&self, num: usize) -> &[usize] {
+        &self.part_symbols[num]
+    }
+
+    /// Groups the parts that are linked through shared symbols.
+    ///
+    /// Numbers touching no symbol are left out.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.numbers.len()];
+        let mut components = vec![];
+
+        for start in 0..self.numbers.len() {
+            if visited[start] || self.part_symbols[start].is_empty() {
+                continue;
+            }
+
+            visited[start] = true;
+            let mut component = vec![];
+            let mut stack = vec![start];
+            while let Some(num) = stack.pop() {
+                component.push(num);
+
+                for sym in &self.part_symbols[num] {
+                    for next in &self.symbol_parts[*sym] {
+                        if !visited[*next] {
+                            visited[*next] = true;
+                            stack.push(*next);
+                        }
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Renders the graph in the Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let escape = |label: String| label.replace('\\', "\\\\").replace('"', "\\\"");
+
+        let mut dot = String::from("graph schematic {\n");
+        for (sym, entry) in self.symbols.iter().enumerate() {
+            let label = escape(entry.val.to_string());
+            writeln!(dot, "    s{} [label=\"{}\", shape=box];", sym, label).unwrap();
+        }
+        for (num, entry) in self.numbers.iter().enumerate() {
+            writeln!(dot, "    n{} [label=\"{}\"];", num, entry.val).unwrap();
+        }
+        for (sym, parts) in self.symbol_parts.iter().enumerate() {
+            for num in parts {
+                writeln!(dot, "    s{} -- n{};", sym, num).unwrap();
+            }
+        }
+        dot.push('}');
+
+        dot
+    }
+
+    pub fn to_json(&self) -> Value {
+        let symbols: Vec<_> = self
+            .symbols
+            .iter()
+            .map(|entry| json!({ "symbol": entry.val, "x": entry.x, "y": entry.y }))
+            .collect();
+        let numbers: Vec<_> = self
+            .numbers
+            .iter()
+            .map(
+                |entry| json!({ "value": entry.val, "x": entry.x, "y": entry.y, "len": entry.len }),
+            )
+            .collect();
+        let edges: Vec<_> = self
+            .symbol_parts
+            .iter()
+            .enumerate()
+            .flat_map(|(sym, parts)| {
+                parts
+                    .iter()
+                    .map(move |num| json!({ "symbol": sym, "number": num }))
+            })
+            .collect();
+
+        json!({ "symbols": symbols, "numbers": numbers, "edges": edges })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use burn::backend::NdArray;
+    use test_case::test_case;
+
+    use crate::{parse_schematic, EXAMPLE};
+
+    #[test_case(EXAMPLE, 0 => vec![0, 2])]
+    #[test_case(EXAMPLE, 1 => vec![3])]
+    #[test_case(EXAMPLE, 5 => vec![7, 9])]
+    #[test_case("1*2\n.22", 0 => vec![0, 1, 2])]
+    fn test_symbol_parts(lines: &str, sym: usize) -> Vec<usize> {
+        let schematic = parse_schematic::<NdArray>(lines).unwrap();
+
+        schematic.graph().symbol_parts(sym).to_vec()
+    }
+
+    #[test_case(EXAMPLE, 0 => vec![0])]
+    #[test_case(EXAMPLE, 1 => Vec::<usize>::new())]
+    #[test_case("#12*\n.-..", 0 => vec![0, 1, 2])]
+    fn test_part_symbols(lines: &str, num: usize) -> Vec<usize> {
+        let schematic = parse_schematic::<NdArray>(lines).unwrap();
+
+        schematic.graph().part_symbols(num).to_vec()
+    }
+
+    #[test_case(EXAMPLE => vec![vec![0, 2], vec![3], vec![4], vec![6], vec![7, 9], vec![8]])]
+    #[test_case("1*2#3\n....4\n5...." => vec![vec![0, 1, 2, 3]])]
+    fn test_components(lines: &str) -> Vec<Vec<usize>> {
+        let schematic = parse_schematic::<NdArray>(lines).unwrap();
+
+        schematic.graph().components()
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let schematic = parse_schematic::<NdArray>("12\"\n..3").unwrap();
+
+        assert_eq!(
+            schematic.graph().to_dot(),
+            "graph schematic {
+    s0 [label=\"\\\"\", shape=box];
+    n0 [label=\"12\"];
+    n1 [label=\"3\"];
+    s0 -- n0;
+    s0 -- n1;
+}"
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let schematic = parse_schematic::<NdArray>("12*\n...").unwrap();
+
+        assert_eq!(
+            schematic.graph().to_json().to_string(),
+            r#"{"edges":[{"number":0,"symbol":0}],"numbers":[{"len":2,"value":12,"x":0,"y":0}],"symbols":[{"symbol":"*","x":2,"y":0}]}"#
+        );
+    }
+}
@@ -0,0 +1,426 @@
+use std::marker::PhantomData;
+
+use aoc_core::{ParseError, Solution};
+#[cfg(not(feature = "wgpu"))]
+use burn::backend::NdArray;
+#[cfg(feature = "wgpu")]
+use burn::backend::{wgpu::OpenGl, Wgpu};
+use burn::tensor::backend::Backend;
+use burn::tensor::ops::ConvOptions;
+use burn::tensor::Int;
+use burn::tensor::{module::conv2d, Tensor};
+use itertools::Itertools;
+
+mod gear;
+mod graph;
+mod scalar;
+
+pub use gear::{GearRule, Reduce};
+pub use graph::PartGraph;
+
+/// Backend used when none is specified, disable the `wgpu` feature to run on the CPU only.
+#[cfg(feature = "wgpu")]
+pub type DefaultBackend = Wgpu<OpenGl>;
+#[cfg(not(feature = "wgpu"))]
+pub type DefaultBackend = NdArray;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Schematic;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_schematic(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer, ParseError> {
+        Ok(input.get_part_nums().iter().sum())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer, ParseError> {
+        Ok(input.get_gear_ratios()?.iter().sum())
+    }
+}
+
+/// A number or symbol in the schematic, starting at `x`/`y` and spanning `len` columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry<T> {
+    pub val: T,
+    pub x: u64,
+    pub y: u64,
+    pub len: u64,
+    /// 1-based character column of the start in the untrimmed input line, for errors
+    pub column: usize,
+}
+
+/// How a [`Schematic`] finds its parts and gears.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Convolutions over the schematic as tensors on the backend
+    #[default]
+    Tensor,
+    /// Direct scan of the neighbouring grid cells
+    Scalar,
+}
+
+#[derive(Debug)]
+pub struct Schematic<B: Backend = DefaultBackend> {
+    numbers: Vec<Entry<u64>>,
+    symbols: Vec<Entry<char>>,
+    rows: usize,
+    cols: usize,
+    strategy: Strategy,
+    backend: PhantomData<B>,
+}
+
+pub fn parse_schematic<B: Backend>(input: &str) -> Result<Schematic<B>, ParseError> {
+    let mut nums = vec![];
+    let mut syms = vec![];
+
+    let cols = input
+        .lines()
+        .next()
+        .ok_or_else(|| ParseError::new(1, 1, "", "a schematic row"))?
+        .trim()
+        .len();
+
+    for (y, raw_line) in (0u64..).zip(input.lines()) {
+        let line = raw_line.trim();
+        if line.len() != cols {
+            return Err(ParseError::at(
+                y as usize,
+                raw_line,
+                line,
+                format!("a row of {} columns", cols),
+            ));
+        }
+
+        // parse nums
+        let line_ptr = line.as_ptr() as u64;
+        let indent = line.as_ptr() as usize - raw_line.as_ptr() as usize;
+        let column = |x: usize| raw_line[..indent + x].chars().count() + 1;
+
+        let num_splits = line
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|e| !e.is_empty());
+
+        for num in num_splits {
+            // calculate offset from beginning of line
+            let val: u64 = num.parse().map_err(|_| {
+                ParseError::at(y as usize, raw_line, num, "a number fitting into 64 bits")
+            })?;
+            let num_ptr = num.as_ptr() as u64;
+            let x = num_ptr - line_ptr;
+            nums.push(Entry {
+                val,
+                x,
+                y,
+                len: num.len() as u64,
+                column: column(x as usize),
+            })
+        }
+
+        // parse syms, one entry per character so neighbouring symbols stay apart
+        let syms_in_line = line
+            .char_indices()
+            .filter(|(_, c)| !c.is_ascii_digit() && *c != '.');
+        for (x, val) in syms_in_line {
+            syms.push(Entry {
+                val,
+                x: x as u64,
+                y,
+                len: 1,
+                column: column(x),
+            })
+        }
+    }
+
+    Ok(Schematic {
+        numbers: nums,
+        symbols: syms,
+        cols,
+        rows: input.lines().count(),
+        strategy: Strategy::default(),
+        backend: PhantomData,
+    })
+}
+
+impl<B: Backend> Schematic<B> {
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Returns every number adjacent to a symbol exactly once, in reading order.
+    pub fn get_parts(&self) -> Vec<&Entry<u64>> {
+        match self.strategy {
+            Strategy::Tensor => self.tensor_parts(),
+            Strategy::Scalar => self.scalar_parts(),
+        }
+    }
+
+    pub fn get_part_nums(&self) -> Vec<u64> {
+        self.get_parts()
+            .into_iter()
+            .map(|entry| entry.val)
+            .collect()
+    }
+
+    pub fn get_gear_ratios(&self) -> Result<Vec<u64>, ParseError> {
+        self.get_gears(&GearRule::default())
+    }
+
+    /// Returns the value of every gear matching `rule`, in reading order.
+    ///
+    /// Fails at the first gear whose value does not fit into a `u64`.
+    pub fn get_gears(&self, rule: &GearRule) -> Result<Vec<u64>, ParseError> {
+        match self.strategy {
+            Strategy::Tensor => self.tensor_gears(rule),
+            Strategy::Scalar => self.scalar_gears(rule),
+        }
+    }
+
+    /// Builds a matrix with the 1-based index of the number covering each cell
+    /// and one with the symbol in each cell.
+    fn as_tensors(&self) -> (Tensor<B, 4, Int>, Tensor<B, 4, Int>) {
+        let mut nums_matrix = Tensor::<B, 4, Int>::zeros([1, 1, self.rows, self.cols]);
+
+        for (idx, entry) in self.numbers.iter().enumerate() {
+            let len = entry.len as usize;
+            let x = entry.x as usize;
+            let y = entry.y as usize;
+            nums_matrix = nums_matrix.slice_assign(
+                [0..1, 0..1, y..y + 1, x..x + len],
+                Tensor::<B, 4, Int>::ones([1, 1, 1, len]) * (idx + 1) as u32,
+            );
+        }
+
+        let mut syms_matrix = Tensor::<B, 4, Int>::zeros([1, 1, self.rows, self.cols]);
+        for entry in self.symbols.iter() {
+            let x = entry.x as usize;
+            let y = entry.y as usize;
+            syms_matrix = syms_matrix.slice_assign(
+                [0..1, 0..1, y..y + 1, x..x + 1],
+                Tensor::<B, 4, Int>::ones([1, 1, 1, 1]) * entry.val as u32,
+            );
+        }
+
+        (nums_matrix, syms_matrix)
+    }
+
+    fn tensor_parts(&self) -> Vec<&Entry<u64>> {
+        let (nums_matrix, syms_matrix) = self.as_tensors();
+
+        let conv3x3 = Tensor::<B, 4>::ones([1, 1, 3, 3]);
+
+        let conv_res = conv2d(
+            syms_matrix.float().clamp(0., 1.),
+            conv3x3,
+            None,
+            ConvOptions::new([1, 1], [1, 1], [1, 1], 1),
+        )
+        .clamp(0., 1.);
+
+        let parts_tensor = conv_res.mul(nums_matrix.float()).int();
+
+        // every adjacent cell of a number carries its index, so each number is collected once
+        parts_tensor
+            .into_data()
+            .convert::<i64>()
+            .value
+            .into_iter()
+            .filter(|idx| *idx > 0)
+            .map(|idx| idx as usize - 1)
+            .sorted_unstable()
+            .dedup()
+            .map(|idx| &self.numbers[idx])
+            .collect()
+    }
+
+    fn tensor_gears(&self, rule: &GearRule) -> Result<Vec<u64>, ParseError> {
+        let (nums_matrix, syms_matrix) = self.as_tensors();
+
+        let gear_pos_mask = syms_matrix.clone().equal_elem(rule.symbol as u32);
+        let syms_matrix = syms_matrix
+            .mask_fill(gear_pos_mask.clone().bool_not(), 0)
+            .float()
+            .clamp(0., 1.);
+
+        let conv_gear3x3 = Tensor::<B, 2, Int>::from_ints([[1, 1, 1], [1, 0, 1], [1, 1, 1]])
+            .float()
+            .reshape([1, 1, 3, 3]);
+
+        let gear_nums = conv2d(
+            syms_matrix,
+            conv_gear3x3,
+            None,
+            ConvOptions::new([1, 1], [1, 1], [1, 1], 1),
+        )
+        .clamp(0., 1.);
+
+        // number cells next to a gear carry the index of their number
+        let gear_parts = gear_nums.mul(nums_matrix.float());
+
+        let mask_vec: Vec<bool> = gear_pos_mask.into_data().value;
+        let nums_vec: Vec<i64> = gear_parts.int().into_data().convert().value;
+
+        let grid = self.symbol_grid();
+        let mut gears = vec![];
+        for y in 0..self.rows as i64 {
+            for x in 0..self.cols as i64 {
+                if !mask_vec[y as usize * self.cols + x as usize] {
+                    continue;
+                }
+
+                let parts: Vec<u64> = (y - 1..=y + 1)
+                    .cartesian_product(x - 1..=x + 1)
+                    .filter(|(j, i)| {
+                        (0..self.rows as i64).contains(j) && (0..self.cols as i64).contains(i)
+                    })
+                    .map(|(j, i)| nums_vec[j as usize * self.cols + i as usize])
+                    .filter(|idx| *idx > 0)
+                    .sorted_unstable()
+                    .dedup()
+                    .map(|idx| self.numbers[idx as usize - 1].val)
+                    .collect();
+
+                let symbol = grid[y as usize * self.cols + x as usize].expect("gears are symbols");
+                gears.extend(rule.evaluate(&self.symbols[symbol], &parts)?);
+            }
+        }
+
+        Ok(gears)
+    }
+}
+
+/// The schematic from the puzzle description, shared by the tests of all modules.
+#[cfg(test)]
+const EXAMPLE: &str = "467..114..
+        ...*......
+        ..35..633.
+        ......#...
+        617*......
+        .....+.58.
+        ..592.....
+        ......755.
+        ...$.*....
+        .664.598..";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use burn::backend::NdArray;
+    use test_case::test_case;
+
+    #[test_case(EXAMPLE)]
+    fn test_parse(lines: &str) {
+        let schematic = parse_schematic::<NdArray>(lines).unwrap();
+        dbg!(schematic);
+    }
+
+    #[test_case(NdArray::<f32>::default(), EXAMPLE, vec![467, 35, 633, 617, 592, 755, 664, 598])]
+    #[cfg_attr(
+        feature = "wgpu",
+        test_case(Wgpu::<OpenGl>::default(), EXAMPLE, vec![467, 35, 633, 617, 592, 755, 664, 598])
+    )]
+    fn test_parts<B: Backend>(_backend: B, lines: &str, desired: Vec<u64>) {
+        let schematic = parse_schematic::<B>(lines).unwrap();
+        let part_nums = schematic.get_part_nums();
+
+        assert_eq!(part_nums, desired);
+    }
+
+    #[test_case(NdArray::<f32>::default(), ".....\n*123*\n....." => vec![(123, 1, 1)])]
+    #[test_case(NdArray::<f32>::default(), "#....\n.1234\n....$" => vec![(1234, 1, 1)])]
+    #[test_case(NdArray::<f32>::default(), "12.....7\n*......#\n........\n5......9\n.$......" => vec![(12, 0, 0), (7, 7, 0), (5, 0, 3)])]
+    #[test_case(NdArray::<f32>::default(), "35.35\n..*..\n35..." => vec![(35, 0, 0), (35, 3, 0), (35, 0, 2)])]
+    #[test_case(NdArray::<f32>::default(), "007*.\n....0" => vec![(7, 0, 0), (0, 4, 1)])]
+    #[test_case(NdArray::<f32>::default(), "...*#5\n......" => vec![(5, 5, 0)])]
+    #[test_case(NdArray::<f32>::default(), "1#*..\n....2\n.3@%." => vec![(1, 0, 0), (2, 4, 1), (3, 1, 2)])]
+    #[cfg_attr(
+        feature = "wgpu",
+        test_case(Wgpu::<OpenGl>::default(), ".....\n*123*\n....." => vec![(123, 1, 1)])
+    )]
+    #[cfg_attr(
+        feature = "wgpu",
+        test_case(Wgpu::<OpenGl>::default(), "35.35\n..*..\n35..." => vec![(35, 0, 0), (35, 3, 0), (35, 0, 2)])
+    )]
+    fn test_parts_once<B: Backend>(_backend: B, lines: &str) -> Vec<(u64, u64, u64)> {
+        let schematic = parse_schematic::<B>(lines).unwrap();
+        let parts = schematic.get_parts();
+
+        let scalar = parse_schematic::<B>(lines)
+            .unwrap()
+            .with_strategy(Strategy::Scalar);
+        assert_eq!(scalar.get_parts(), parts);
+
+        parts
+            .into_iter()
+            .map(|entry| (entry.val, entry.x, entry.y))
+            .collect()
+    }
+
+    #[test_case(NdArray::<f32>::default(), EXAMPLE, vec![16345, 451490])]
+    #[cfg_attr(
+        feature = "wgpu",
+        test_case(Wgpu::<OpenGl>::default(), EXAMPLE, vec![16345, 451490])
+    )]
+    fn test_ratios<B: Backend>(_backend: B, lines: &str, desired: Vec<u64>) {
+        let schematic = parse_schematic::<B>(lines).unwrap();
+        let ratios = schematic.get_gear_ratios();
+
+        assert_eq!(ratios, Ok(desired));
+    }
+
+    #[test_case(NdArray::<f32>::default(), EXAMPLE, GearRule::new('*', 1..=2, Reduce::Sum) => Ok(vec![502, 617, 1353]))]
+    #[test_case(NdArray::<f32>::default(), "1.2\n.#.\n3.4", GearRule::new('#', 3..=usize::MAX, Reduce::Sum) => Ok(vec![10]))]
+    #[test_case(NdArray::<f32>::default(), "12*12", GearRule::new('*', 2..=2, Reduce::Max) => Ok(vec![12]))]
+    #[test_case(NdArray::<f32>::default(), "123\n.*.", GearRule::new('*', 1..=1, Reduce::Product) => Ok(vec![123]))]
+    #[test_case(NdArray::<f32>::default(), "...*\n5...", GearRule::new('*', 0..=0, Reduce::Sum) => Ok(vec![0]))]
+    #[test_case(NdArray::<f32>::default(), "4294967296*4294967296", GearRule::default() => Err(ParseError::new(1, 11, "*", "a gear value fitting into 64 bits")))]
+    #[test_case(NdArray::<f32>::default(), ".....................\n    4294967296*4294967296", GearRule::default() => Err(ParseError::new(2, 15, "*", "a gear value fitting into 64 bits")))]
+    #[test_case(NdArray::<f32>::default(), ".....................\n\u{3000} 4294967296*4294967296", GearRule::default() => Err(ParseError::new(2, 13, "*", "a gear value fitting into 64 bits")))]
+    #[cfg_attr(
+        feature = "wgpu",
+        test_case(Wgpu::<OpenGl>::default(), EXAMPLE, GearRule::new('*', 1..=2, Reduce::Sum) => Ok(vec![502, 617, 1353]))
+    )]
+    #[cfg_attr(
+        feature = "wgpu",
+        test_case(Wgpu::<OpenGl>::default(), "...*\n5...", GearRule::new('*', 0..=0, Reduce::Sum) => Ok(vec![0]))
+    )]
+    fn test_gears<B: Backend>(
+        _backend: B,
+        lines: &str,
+        rule: GearRule,
+    ) -> Result<Vec<u64>, ParseError> {
+        let schematic = parse_schematic::<B>(lines).unwrap();
+        let gears = schematic.get_gears(&rule);
+
+        let scalar = parse_schematic::<B>(lines)
+            .unwrap()
+            .with_strategy(Strategy::Scalar);
+        assert_eq!(scalar.get_gears(&rule), gears);
+
+        gears
+    }
+
+    #[test_case(EXAMPLE, 4361, 467835)]
+    fn test_solution(lines: &str, part1: u64, part2: u64) {
+        let schematic = Day03::parse(lines).unwrap();
+
+        assert_eq!(Day03::part1(&schematic), Ok(part1));
+        assert_eq!(Day03::part2(&schematic), Ok(part2));
+    }
+
+    #[test_case("" => ParseError::new(1, 1, "", "a schematic row"))]
+    #[test_case("467..\n...*\n" => ParseError::new(2, 1, "...*", "a row of 5 columns"))]
+    #[test_case(
+        "..*....................\n.123456789012345678901."
+        => ParseError::new(2, 2, "123456789012345678901", "a number fitting into 64 bits")
+    )]
+    fn test_parse_error(lines: &str) -> ParseError {
+        parse_schematic::<NdArray>(lines).unwrap_err()
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use aoc_core::ParseError;
+use burn::tensor::backend::Backend;
+use itertools::Itertools;
+
+use crate::{Entry, GearRule, Schematic};
+
+impl<B: Backend> Schematic<B> {
+    /// Looks up the symbols in the 8-neighbourhood of a number, in row-major order.
+    pub(crate) fn adjacent_symbols<'a>(
+        &'a self,
+        grid: &'a [Option<usize>],
+        entry: &Entry<u64>,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let x = entry.x as i64;
+        let y = entry.y as i64;
+        let len = entry.len as i64;
+
+        (y - 1..=y + 1)
+            .cartesian_product(x - 1..=x + len)
+            .filter(|(j, i)| (0..self.rows as i64).contains(j) && (0..self.cols as i64).contains(i))
+            .filter_map(|(j, i)| grid[j as usize * self.cols + i as usize])
+    }
+
+    /// Maps every cell to the index of the symbol occupying it.
+    pub(crate) fn symbol_grid(&self) -> Vec<Option<usize>> {
+        let mut grid = vec![None; self.rows * self.cols];
+        for (idx, entry) in self.symbols.iter().enumerate() {
+            grid[entry.y as usize * self.cols + entry.x as usize] = Some(idx);
+        }
+
+        grid
+    }
+
+    pub(crate) fn scalar_parts(&self) -> Vec<&Entry<u64>> {
+        let grid = self.symbol_grid();
+
+        self.numbers
+            .iter()
+            .filter(|entry| self.adjacent_symbols(&grid, entry).next().is_some())
+            .collect()
+    }
+
+    pub(crate) fn scalar_gears(&self, rule: &GearRule) -> Result<Vec<u64>, ParseError> {
+        let grid = self.symbol_grid();
+
+        let mut gears: HashMap<usize, Vec<u64>> = self
+            .symbols
+            .iter()
+            .positions(|sym| sym.val == rule.symbol)
+            .map(|sym| (sym, vec![]))
+            .collect();
+        for entry in self.numbers.iter() {
+            for sym in self.adjacent_symbols(&grid, entry) {
+                if let Some(parts) = gears.get_mut(&sym) {
+                    parts.push(entry.val);
+                }
+            }
+        }
+
+        gears
+            .into_iter()
+            .sorted_by_key(|(sym, _)| *sym)
+            .filter_map(|(sym, parts)| rule.evaluate(&self.symbols[sym], &parts).transpose())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use burn::backend::NdArray;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use test_case::test_case;
+
+    use crate::{parse_schematic, Reduce, Strategy, EXAMPLE};
+
+    const SYMBOLS: &[u8] = b"**#+$/@=%&-";
+
+    fn random_schematic(rng: &mut StdRng, rows: usize, cols: usize) -> String {
+        let mut grid = vec![vec![b'.'; cols]; rows];
+
+        for _ in 0..rows * cols / 4 {
+            let len = rng.gen_range(1..=3);
+            let y = rng.gen_range(0..rows);
+            let x = rng.gen_range(0..=cols - len);
+
+            let free = (x.saturating_sub(1)..(x + len + 1).min(cols)).all(|i| grid[y][i] == b'.');
+            if free {
+                for cell in &mut grid[y][x..x + len] {
+                    *cell = rng.gen_range(b'0'..=b'9');
+                }
+            }
+        }
+
+        for _ in 0..rows * cols / 6 {
+            let y = rng.gen_range(0..rows);
+            let x = rng.gen_range(0..cols);
+            if grid[y][x] == b'.' {
+                grid[y][x] = SYMBOLS[rng.gen_range(0..SYMBOLS.len())];
+            }
+        }
+
+        grid.into_iter()
+            .map(|row| String::from_utf8(row).unwrap())
+            .join("\n")
+    }
+
+    #[test_case(EXAMPLE => vec![467, 35, 633, 617, 592, 755, 664, 598])]
+    fn test_parts(lines: &str) -> Vec<u64> {
+        parse_schematic::<NdArray>(lines)
+            .unwrap()
+            .with_strategy(Strategy::Scalar)
+            .get_part_nums()
+    }
+
+    #[test_case(EXAMPLE => vec![16345, 451490])]
+    fn test_ratios(lines: &str) -> Vec<u64> {
+        parse_schematic::<NdArray>(lines)
+            .unwrap()
+            .with_strategy(Strategy::Scalar)
+            .get_gear_ratios()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_matches_tensor() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let rules = [
+            GearRule::default(),
+            GearRule::new('#', 0..=usize::MAX, Reduce::Sum),
+            GearRule::new('*', 1..=3, Reduce::Custom(|parts| parts[0])),
+        ];
+
+        for _ in 0..100 {
+            let rows = rng.gen_range(3..=16);
+            let cols = rng.gen_range(3..=16);
+            let lines = random_schematic(&mut rng, rows, cols);
+
+            let tensor = parse_schematic::<NdArray>(&lines).unwrap();
+            let scalar = parse_schematic::<NdArray>(&lines)
+                .unwrap()
+                .with_strategy(Strategy::Scalar);
+
+            assert_eq!(scalar.get_parts(), tensor.get_parts(), "{}", lines);
+            for rule in &rules {
+                assert_eq!(scalar.get_gears(rule), tensor.get_gears(rule), "{}", lines);
+            }
+        }
+    }
+}
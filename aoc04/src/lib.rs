@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+use aoc_core::{ParseError, Solution};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Card>;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer, ParseError> {
+        Ok(input.iter().map(|c| c.points()).sum())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer, ParseError> {
+        Ok(collect_cards(input.clone()).iter().map(|c| c.count).sum())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Card {
+    winning_nums: HashSet<u64>,
+    my_nums: HashSet<u64>,
+    count: u64,
+}
+
+impl Card {
+    pub fn winning_nums(&self) -> Vec<u64> {
+        self.my_nums
+            .intersection(&self.winning_nums)
+            .copied()
+            .collect()
+    }
+
+    pub fn points(&self) -> u64 {
+        let len = self.winning_nums().len();
+        if len > 0 {
+            2u64.pow(len as u32 - 1)
+        } else {
+            0
+        }
+    }
+}
+
+pub fn parse(doc: &str) -> Result<Vec<Card>, ParseError> {
+    doc.lines()
+        .enumerate()
+        .map(|(idx, raw_line)| {
+            let err =
+                |snippet: &str, expected: &str| ParseError::at(idx, raw_line, snippet, expected);
+            let parse_nums = |nums: &str| {
+                nums.split(' ')
+                    .filter(|v| !v.is_empty())
+                    .map(|v| v.parse::<u64>().map_err(|_| err(v, "a number")))
+                    .collect::<Result<HashSet<_>, _>>()
+            };
+
+            let line = raw_line.trim();
+            let (pre, rest) = line
+                .split_once(':')
+                .ok_or_else(|| err(&line[line.len()..], "`:`"))?;
+            let id = pre
+                .strip_prefix("Card")
+                .ok_or_else(|| err(pre, "`Card <id>`"))?
+                .trim();
+            id.parse::<u64>().map_err(|_| err(id, "a card id"))?;
+            let (win, own) = rest
+                .split_once('|')
+                .ok_or_else(|| err(&rest[rest.len()..], "`|`"))?;
+
+            Ok(Card {
+                winning_nums: parse_nums(win)?,
+                my_nums: parse_nums(own)?,
+                count: 1,
+            })
+        })
+        .collect()
+}
+
+pub fn collect_cards(cards: Vec<Card>) -> Vec<Card> {
+    let mut cards_res = cards.clone();
+    for idx in 0..cards_res.len() {
+        let card = &cards_res[idx];
+        let num_winning = card.winning_nums().len();
+        if num_winning == 0 {
+            continue;
+        }
+
+        let cnt = card.count;
+
+        for i in idx + 1..=idx + num_winning {
+            if let Some(c) = cards_res.get_mut(i) {
+                c.count += cnt;
+            }
+        }
+    }
+
+    cards_res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_case::test_case;
+
+    #[test_case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53")]
+    #[test_case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19")]
+    #[test_case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1")]
+    #[test_case("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83")]
+    #[test_case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36")]
+    #[test_case("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11")]
+    fn test_parse(line: &str) {
+        let cards = parse(line).unwrap();
+
+        assert!(cards.len() == 1);
+    }
+
+    #[test_case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", vec![48, 83, 17, 86])]
+    #[test_case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19", vec![32, 61])]
+    #[test_case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1", vec![1, 21])]
+    #[test_case("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83", vec![84])]
+    #[test_case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", vec![])]
+    #[test_case("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11", vec![])]
+    fn get_winning_nums(line: &str, mut desired: Vec<u64>) {
+        let card = parse(line).unwrap().pop().unwrap();
+
+        let mut winning: Vec<_> = card.winning_nums();
+        desired.sort();
+        winning.sort();
+        assert_eq!(winning, desired);
+    }
+
+    #[test_case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53" => 8)]
+    #[test_case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19" => 2)]
+    #[test_case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1" => 2)]
+    #[test_case("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83" => 1)]
+    #[test_case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36" => 0)]
+    #[test_case("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11" => 0)]
+    fn test_points(line: &str) -> u64 {
+        let card = parse(line).unwrap().pop().unwrap();
+
+        card.points()
+    }
+
+    #[test_case(
+        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+        Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+        Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+        Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+        Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
+        => 30
+    )]
+    fn test_collect_cards(doc: &str) -> u64 {
+        let cards = parse(doc).unwrap();
+
+        let cards = collect_cards(cards);
+        let cards_total: u64 = cards.iter().map(|c| c.count).sum();
+        cards_total
+    }
+
+    #[test_case(
+        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+        Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+        Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+        Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+        Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        13,
+        30
+    )]
+    fn test_solution(doc: &str, part1: u64, part2: u64) {
+        let cards = Day04::parse(doc).unwrap();
+
+        assert_eq!(Day04::part1(&cards), Ok(part1));
+        assert_eq!(Day04::part2(&cards), Ok(part2));
+    }
+
+    #[test_case("Card 1 41 48 | 83 86" => ParseError::new(1, 21, "", "`:`"))]
+    #[test_case("Card 1: 41 48 83 86" => ParseError::new(1, 20, "", "`|`"))]
+    #[test_case("Card x: 41 48 | 83 86" => ParseError::new(1, 6, "x", "a card id"))]
+    #[test_case("Card   : 41 | 83" => ParseError::new(1, 5, "", "a card id"))]
+    #[test_case("Crad 1: 41 48 | 83 86" => ParseError::new(1, 1, "Crad 1", "`Card <id>`"))]
+    #[test_case("Card 1: 41 48 | 83 x6" => ParseError::new(1, 20, "x6", "a number"))]
+    #[test_case("Card 1: 41 | 83\nCard 2: 4- | 1" => ParseError::new(2, 9, "4-", "a number"))]
+    fn test_parse_error(doc: &str) -> ParseError {
+        parse(doc).unwrap_err()
+    }
+}